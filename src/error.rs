@@ -0,0 +1,475 @@
+use crate::models::board::BoardSize;
+use crate::models::gobblet::PieceSize;
+use crate::models::gomoku::Forbidden;
+use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::utils::definitions::DefinitionError;
+use crate::utils::export::RecordError;
+use crate::utils::notation::{cell_name, cube_cell_name, PositionError};
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Input(InputError),
+    Move(MoveError),
+    Position(PositionError),
+    Record(RecordError),
+    Definition(DefinitionError),
+    NoMoveAvailable(Player),
+    UnsupportedBoardSize(Variant, BoardSize),
+    UnsupportedPlayerCount(Variant, usize),
+    UnsupportedShape(Variant),
+    UnsupportedTorus(Variant),
+    TooManyBlockedCells(usize, usize),
+    UnsupportedGomokuRules(Variant),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    EndOfInput,
+    NotANumber(String),
+    NotACharacter,
+    NotAlphaNum(String),
+    NotARow(String),
+    NotAColumn(usize),
+    NotAPlayer(char),
+    NotAVariant(String),
+    NotABoardSize(String),
+    MissingBoard,
+    NotACubeCell(String),
+    NotAWildMove(String),
+    NotANumberedMove(String),
+    NotABoardCell(String),
+    NotAMorrisMove(String),
+    NotAGobbletMove(String),
+    NotASpookyMove(String),
+    NotALayer(usize),
+    NotABoard(usize),
+    NotASeat(char),
+    NotAShape(String),
+    NotAGomokuRule(String),
+    NotAnOpening(String),
+    NotASwapChoice(String),
+    NotCells(String, usize),
+    TooManyAttempts,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MoveError {
+    OutOfBounds {
+        row_index: usize,
+        col_index: usize,
+    },
+    Occupied {
+        row_index: usize,
+        col_index: usize,
+        by: Player,
+    },
+    Blocked {
+        row_index: usize,
+        col_index: usize,
+    },
+    NumberPresent {
+        row_index: usize,
+        col_index: usize,
+        number: u8,
+    },
+    NumberUnavailable(u8),
+    Forbidden {
+        row_index: usize,
+        col_index: usize,
+        reason: Forbidden,
+    },
+    WrongPlayer {
+        expected: Player,
+        attempted: Player,
+    },
+    SymbolPresent {
+        row_index: usize,
+        col_index: usize,
+        symbol: Symbol,
+    },
+    OutOfCube((usize, usize, usize)),
+    OccupiedInCube {
+        position: (usize, usize, usize),
+        by: Player,
+    },
+    WrongBoard {
+        expected: (usize, usize),
+        attempted: (usize, usize),
+    },
+    BoardDecided((usize, usize)),
+    ColumnFull(usize),
+    Floating((usize, usize)),
+    AllPiecesPlaced,
+    PiecesLeftToPlace,
+    NotYourPiece((usize, usize)),
+    NotAdjacent {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    SameCell((usize, usize)),
+    StayedPut((usize, usize)),
+    NoPieceLeft(PieceSize),
+    CannotCover {
+        row_index: usize,
+        col_index: usize,
+        size: PieceSize,
+        covered: PieceSize,
+    },
+    MustPlaceClassically,
+    MustPlaceSpooky,
+    CollapsePending,
+    NoCollapsePending,
+    NotInPair((usize, usize)),
+    NoSuchBoard(usize),
+    BoardDead(usize),
+    GameOver,
+    // A recorded move that's missing what it needs to be replayed.
+    Unrecorded {
+        position: (usize, usize),
+        missing: &'static str,
+    },
+}
+
+impl Error {
+    pub fn is_end_of_input(&self) -> bool {
+        matches!(self, Error::Input(InputError::EndOfInput))
+    }
+}
+
+impl InputError {
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, InputError::EndOfInput | InputError::TooManyAttempts)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+            Error::Input(error) => write!(f, "{}", error),
+            Error::Move(error) => write!(f, "{}", error),
+            Error::Position(error) => write!(f, "{}", error),
+            Error::Record(error) => write!(f, "{}", error),
+            Error::Definition(error) => write!(f, "{}", error),
+            Error::NoMoveAvailable(player) => {
+                write!(f, "There's no move left for player {}", player.character())
+            }
+            Error::UnsupportedBoardSize(variant, size) => write!(
+                f,
+                "The {} rules can't be played on a {} board",
+                variant.name(),
+                size
+            ),
+            Error::UnsupportedPlayerCount(variant, player_count) => write!(
+                f,
+                "The {} rules can't be played by {} players",
+                variant.name(),
+                player_count
+            ),
+            Error::UnsupportedShape(variant) => write!(
+                f,
+                "The {} rules can't be played on a shaped board",
+                variant.name()
+            ),
+            Error::UnsupportedTorus(variant) => {
+                write!(f, "The {} rules can't be played on a torus", variant.name())
+            }
+            Error::TooManyBlockedCells(blocked_cells, open_cells) => write!(
+                f,
+                "Can't block {} cells on a board with {} to play on",
+                blocked_cells, open_cells
+            ),
+            Error::UnsupportedGomokuRules(variant) => write!(
+                f,
+                "The {} rules don't take a Gomoku rule or opening",
+                variant.name()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::EndOfInput => write!(f, "No more input"),
+            InputError::NotANumber(input) => write!(f, "{} is not a number", input),
+            InputError::NotACharacter => write!(f, "Not a character"),
+            InputError::NotAlphaNum(input) => {
+                write!(f, "{} is not a '<letter><number>'", input)
+            }
+            InputError::NotARow(row) => write!(f, "{} is not a row", row),
+            InputError::NotAColumn(col) => write!(f, "{} is not a column", col),
+            InputError::NotAPlayer(character) => write!(f, "{} is not X or O", character),
+            InputError::NotAVariant(input) => write!(f, "{} is not one of the rules", input),
+            InputError::NotABoardSize(input) => {
+                write!(f, "{} is not a board size like 6x7 or 6x7/4", input)
+            }
+            InputError::MissingBoard => {
+                write!(f, "Pick a board and a cell, like 'B2 A1'")
+            }
+            InputError::NotACubeCell(input) => {
+                write!(f, "{} is not a '<layer> <letter><number>'", input)
+            }
+            InputError::NotABoardCell(input) => {
+                write!(f, "{} is not a '<board> <letter><number>'", input)
+            }
+            InputError::NotASpookyMove(input) => {
+                write!(f, "{} is not two cells like A1 B2", input)
+            }
+            InputError::NotAMorrisMove(input) => {
+                write!(f, "{} is not a cell or a move like A1-B1", input)
+            }
+            InputError::NotAGobbletMove(input) => {
+                write!(
+                    f,
+                    "{} is not a size and cell like L B2, or a move like A1-B1",
+                    input
+                )
+            }
+            InputError::NotAWildMove(input) => {
+                write!(f, "{} is not a '<symbol> <letter><number>'", input)
+            }
+            InputError::NotANumberedMove(input) => {
+                write!(f, "{} is not a '<number> <letter><number>'", input)
+            }
+            InputError::NotALayer(layer) => write!(f, "{} is not a layer", layer),
+            InputError::NotABoard(board) => write!(f, "{} is not a board", board),
+            InputError::NotASeat(character) => write!(f, "{} is not one of the seats", character),
+            InputError::NotAShape(mask) => {
+                write!(f, "Can't play on this shape:\n{}", mask.trim_end())
+            }
+            InputError::NotAGomokuRule(input) => {
+                write!(f, "{} is not freestyle, standard or renju", input)
+            }
+            InputError::NotAnOpening(input) => {
+                write!(f, "{} is not plain, swap or swap2", input)
+            }
+            InputError::NotASwapChoice(input) => {
+                write!(f, "{} is not one of the choices", input)
+            }
+            InputError::NotCells(input, count) => {
+                write!(f, "{} is not {} cells like J10 K11", input, count)
+            }
+            InputError::TooManyAttempts => write!(f, "Too many invalid attempts, giving up"),
+        }
+    }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::OutOfBounds {
+                row_index,
+                col_index,
+            } => write!(
+                f,
+                "Row {}, column {} isn't on the board",
+                row_index + 1,
+                col_index + 1
+            ),
+            MoveError::Occupied {
+                row_index,
+                col_index,
+                by,
+            } => write!(
+                f,
+                "Player {} has already taken {}",
+                by.character(),
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::Blocked {
+                row_index,
+                col_index,
+            } => write!(f, "{} is blocked", cell_name(*row_index, *col_index)),
+            MoveError::NumberPresent {
+                row_index,
+                col_index,
+                number,
+            } => write!(
+                f,
+                "There's already a {} on {}",
+                number,
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::NumberUnavailable(number) => {
+                write!(f, "{} isn't one of your numbers left", number)
+            }
+            MoveError::Forbidden {
+                row_index,
+                col_index,
+                reason,
+            } => write!(
+                f,
+                "Black can't play {} under Renju rules: {}",
+                cell_name(*row_index, *col_index),
+                reason.description()
+            ),
+            MoveError::SymbolPresent {
+                row_index,
+                col_index,
+                symbol,
+            } => write!(
+                f,
+                "There's already an {} on {}",
+                symbol.character(),
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::WrongPlayer {
+                expected,
+                attempted,
+            } => write!(
+                f,
+                "It's {}'s turn, not {}'s",
+                expected.character(),
+                attempted.character()
+            ),
+            MoveError::OutOfCube(position) => {
+                write!(f, "{} isn't in the cube", cube_cell_name(*position))
+            }
+            MoveError::OccupiedInCube { position, by } => write!(
+                f,
+                "Player {} has already taken {}",
+                by.character(),
+                cube_cell_name(*position)
+            ),
+            MoveError::WrongBoard {
+                expected,
+                attempted,
+            } => write!(
+                f,
+                "You have to play in board {}, not {}",
+                cell_name(expected.0, expected.1),
+                cell_name(attempted.0, attempted.1)
+            ),
+            MoveError::BoardDecided((board_row, board_col)) => write!(
+                f,
+                "Board {} is already decided",
+                cell_name(*board_row, *board_col)
+            ),
+            MoveError::ColumnFull(col_index) => {
+                write!(f, "Column {} is already full", col_index + 1)
+            }
+            MoveError::Floating((row_index, col_index)) => write!(
+                f,
+                "Nothing holds a mark up on {}; it would fall",
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::AllPiecesPlaced => {
+                write!(f, "All your pieces are down, so move one (e.g., A1-B1)")
+            }
+            MoveError::PiecesLeftToPlace => {
+                write!(f, "Place all your pieces before moving any of them")
+            }
+            MoveError::NotYourPiece((row_index, col_index)) => write!(
+                f,
+                "There's no piece of yours on {}",
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::NotAdjacent { from, to } => write!(
+                f,
+                "{} isn't next to {}",
+                cell_name(to.0, to.1),
+                cell_name(from.0, from.1)
+            ),
+            MoveError::SameCell((row_index, col_index)) => write!(
+                f,
+                "A spooky mark needs two different cells, not {} twice",
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::StayedPut((row_index, col_index)) => write!(
+                f,
+                "The piece on {} has to go to another cell",
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::NoPieceLeft(size) => write!(f, "You have no {} pieces left", size),
+            MoveError::CannotCover {
+                row_index,
+                col_index,
+                size,
+                covered,
+            } => write!(
+                f,
+                "A {} piece can't go over the {} one on {}",
+                size,
+                covered,
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::MustPlaceClassically => {
+                write!(f, "Only one cell is left, so place a single mark there")
+            }
+            MoveError::MustPlaceSpooky => {
+                write!(
+                    f,
+                    "More than one cell is left, so place a spooky mark in two"
+                )
+            }
+            MoveError::CollapsePending => {
+                write!(f, "The entanglement cycle has to collapse first")
+            }
+            MoveError::NoCollapsePending => write!(f, "There's nothing to collapse"),
+            MoveError::NotInPair((row_index, col_index)) => write!(
+                f,
+                "The mark being measured isn't in {}",
+                cell_name(*row_index, *col_index)
+            ),
+            MoveError::NoSuchBoard(board_index) => {
+                write!(f, "There's no board {}", board_index + 1)
+            }
+            MoveError::BoardDead(board_index) => {
+                write!(f, "Board {} is already dead", board_index + 1)
+            }
+            MoveError::GameOver => write!(f, "The game is already over"),
+            MoveError::Unrecorded { position, missing } => write!(
+                f,
+                "The move on {} doesn't say which {}",
+                cell_name(position.0, position.1),
+                missing
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+impl std::error::Error for InputError {}
+impl std::error::Error for MoveError {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(error: InputError) -> Self {
+        Error::Input(error)
+    }
+}
+
+impl From<MoveError> for Error {
+    fn from(error: MoveError) -> Self {
+        Error::Move(error)
+    }
+}
+
+impl From<PositionError> for Error {
+    fn from(error: PositionError) -> Self {
+        Error::Position(error)
+    }
+}
+
+impl From<RecordError> for Error {
+    fn from(error: RecordError) -> Self {
+        Error::Record(error)
+    }
+}
+
+impl From<DefinitionError> for Error {
+    fn from(error: DefinitionError) -> Self {
+        Error::Definition(error)
+    }
+}
@@ -1,89 +1,522 @@
-use crate::models::board::Board;
-use crate::models::cell::Cell;
+use crate::error::{Error, InputError, MoveError};
+use crate::models::board::{Board, BoardSize};
+use crate::models::classic::Classic;
+use crate::models::definition::VariantDefinition;
+use crate::models::game_record::{GameMetadata, GameRecord, GameResult, Participant, RecordedMove};
+use crate::models::gomoku::{GomokuRule, Opening};
+use crate::models::multiplayer::{DEFAULT_PLAYER_COUNT, MAX_PLAYERS, MIN_PLAYERS};
+use crate::models::notakto::DEFAULT_BOARD_COUNT;
 use crate::models::player::Player;
-use crate::utils::ai::best_next_move;
-use crate::utils::ai::current_player;
-use crate::utils::ai::game_over;
-use crate::utils::ai::winning_player_on;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::modes::connect6;
+use crate::modes::custom;
+use crate::modes::gobblet;
+use crate::modes::gomoku;
+use crate::modes::gravity;
+use crate::modes::infinite;
+use crate::modes::morris;
+use crate::modes::multiplayer;
+use crate::modes::notakto;
+use crate::modes::numerical;
+use crate::modes::order_chaos;
+use crate::modes::phantom;
+use crate::modes::quantum;
+use crate::modes::qubic;
+use crate::modes::ultimate;
+use crate::modes::wild;
+use crate::utils::export::append_record;
+use crate::utils::export::parse_all;
+use crate::utils::export::RecordFormat;
 use crate::utils::graphics::clear_screen;
 use crate::utils::graphics::render_board;
 use crate::utils::graphics::render_current_player;
 use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_record_summary;
 use crate::utils::graphics::render_stalemate;
 use crate::utils::graphics::render_winning_player;
-use crate::utils::input::ask_for_cell_position;
 use crate::utils::input::ask_for_player_character;
+use crate::utils::input::ask_for_seat;
+use crate::utils::input::ask_for_variant;
 use crate::utils::input::confirm;
+use crate::utils::input::with_retries;
+use crate::utils::notation::first_player_for;
+use crate::utils::notation::parse_position;
+use crate::utils::notation::position_string;
+use crate::utils::notation::read_position;
+use crate::utils::notation::validate_position;
+use crate::utils::notation::PositionError;
+use crate::utils::validation::classify_position;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn init() {
-    let mut board = Board::new();
+#[derive(Debug, Default)]
+pub struct GameOptions {
+    pub variant: Option<Variant>,
+    pub export_path: Option<String>,
+    pub start_position: Option<(Board, Player)>,
+    pub board_dimensions: Option<(usize, usize)>,
+    pub win_length: Option<usize>,
+    pub board_count: Option<usize>,
+    pub player_count: Option<usize>,
+    pub shape: Option<String>,
+    pub blocked_cells: Option<usize>,
+    pub toroidal: bool,
+    pub gomoku_rule: Option<GomokuRule>,
+    pub opening: Option<Opening>,
+    // Everything loaded from the variant files, and the one `--variant`
+    // picked, if it named one of them.
+    pub definitions: Vec<VariantDefinition>,
+    pub definition: Option<VariantDefinition>,
+}
 
-    clear_screen();
-    render_empty_lines(1);
-    let against_computer = confirm("Play against computer?", true);
-    let player_character = ask_for_player_character("Wanna be X or O?");
-    let computer_goes_first = against_computer && confirm("Can I go first?", true);
-    let first_player = if computer_goes_first {
-        player_character.opponent()
+impl GameOptions {
+    // The variant's own board, with whatever the command line overrode.
+    pub fn board_size(&self, variant: Variant) -> BoardSize {
+        let default_size = variant.default_board_size();
+        let (rows, cols) = self
+            .board_dimensions
+            .unwrap_or((default_size.rows, default_size.cols));
+        BoardSize {
+            rows,
+            cols,
+            win_length: self.win_length.unwrap_or(default_size.win_length),
+        }
+    }
+
+    // The board drawn by `--shape`, or a plain one of the chosen size, with
+    // any `--blocked` cells knocked out at random and wrapped into a torus
+    // by `--torus`.
+    pub fn shaped_board(&self, size: BoardSize) -> Result<Board, Error> {
+        let mut board = match &self.shape {
+            Some(mask) => Board::from_mask(mask, size.win_length)
+                .ok_or_else(|| InputError::NotAShape(mask.clone()))?,
+            None => Board::with_size(size),
+        };
+        board.toroidal = self.toroidal;
+
+        if let Some(blocked_cells) = self.blocked_cells {
+            let open_cells = board.open_cell_count();
+            if blocked_cells >= open_cells {
+                return Err(Error::TooManyBlockedCells(blocked_cells, open_cells));
+            }
+            board.block_random_cells(blocked_cells, random_seed());
+        }
+
+        Ok(board)
+    }
+
+    pub fn with_start_position(mut self, position: &str) -> Result<Self, PositionError> {
+        self.start_position = Some(parse_position(position)?);
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Seating {
+    pub player_character: Player,
+    pub first_player: Player,
+    pub against_computer: bool,
+}
+
+impl Seating {
+    pub fn is_computer(&self, player: &Player) -> bool {
+        self.against_computer && player != &self.player_character
+    }
+
+    pub fn participant(&self, player: &Player, variant: Variant) -> Participant {
+        if self.is_computer(player) {
+            Participant::computer(variant.strategy())
+        } else {
+            Participant::Human
+        }
+    }
+}
+
+pub fn ask_for_seating(first_player: Option<Player>) -> Result<Seating, Error> {
+    let against_computer = confirm("Play against computer?", true)?;
+    let player_character = ask_for_player_character("Wanna be X or O?")?;
+
+    let first_player = match first_player {
+        Some(first_player) => first_player,
+        None => {
+            let computer_goes_first = against_computer && confirm("Can I go first?", true)?;
+            if computer_goes_first {
+                player_character.opponent()
+            } else {
+                player_character.clone()
+            }
+        }
+    };
+
+    Ok(Seating {
+        player_character,
+        first_player,
+        against_computer,
+    })
+}
+
+// With more than two at the table it's one human against the computers, or
+// everyone taking turns at the same keyboard. X always starts.
+pub fn ask_for_multiplayer_seating(player_count: usize) -> Result<Seating, Error> {
+    let against_computer = confirm("Play against computer?", true)?;
+    let player_character = if against_computer {
+        let seats: Vec<&str> = Player::seats(player_count)
+            .iter()
+            .map(|player| player.character())
+            .collect();
+        ask_for_seat(&format!("Which seat? ({})", seats.join(", ")), player_count)?
     } else {
-        player_character.clone()
+        Player::X
     };
 
-    next_turn(
-        &mut board,
-        &player_character,
-        &first_player,
+    Ok(Seating {
+        player_character,
+        first_player: Player::X,
         against_computer,
+    })
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+pub fn new_record(variant: Variant, seating: &Seating) -> GameRecord {
+    GameRecord::new(
+        variant,
+        seating.participant(&Player::X, variant),
+        seating.participant(&Player::O, variant),
+        &seating.first_player,
+    )
+}
+
+pub fn init(options: &GameOptions) -> Result<(), Error> {
+    clear_screen()?;
+    render_empty_lines(1);
+    let (variant, definition) = match options.variant {
+        Some(variant) => (variant, options.definition.clone()),
+        None => ask_for_variant("Which rules?", &options.definitions)?,
+    };
+
+    let board_size = options.board_size(variant);
+    let resizable = matches!(
+        variant,
+        Variant::Gravity
+            | Variant::Multiplayer
+            | Variant::Shaped
+            | Variant::Gomoku
+            | Variant::Connect6
     );
+    if !board_size.is_playable() || (!resizable && board_size != variant.default_board_size()) {
+        return Err(Error::UnsupportedBoardSize(variant, board_size));
+    }
+
+    let shapeable = matches!(variant, Variant::Multiplayer | Variant::Shaped);
+    if !shapeable && (options.shape.is_some() || options.blocked_cells.is_some()) {
+        return Err(Error::UnsupportedShape(variant));
+    }
+    if !shapeable && options.toroidal {
+        return Err(Error::UnsupportedTorus(variant));
+    }
+    if variant != Variant::Gomoku && (options.gomoku_rule.is_some() || options.opening.is_some()) {
+        return Err(Error::UnsupportedGomokuRules(variant));
+    }
+
+    let player_count = options.player_count.unwrap_or(match variant {
+        Variant::Multiplayer => DEFAULT_PLAYER_COUNT,
+        _ => 2,
+    });
+    let allowed_player_counts = match variant {
+        Variant::Multiplayer => MIN_PLAYERS..=MAX_PLAYERS,
+        _ => 2..=2,
+    };
+    if !allowed_player_counts.contains(&player_count) {
+        return Err(Error::UnsupportedPlayerCount(variant, player_count));
+    }
+
+    if !variant.uses_classic_board() && options.start_position.is_some() {
+        return Err(Error::Position(PositionError::UnsupportedVariant(variant)));
+    }
+
+    match variant {
+        Variant::Classic | Variant::Misere => play_classic(variant, options),
+        Variant::Multiplayer | Variant::Shaped => {
+            let board = options.shaped_board(board_size)?;
+            let seating = match variant {
+                Variant::Multiplayer => ask_for_multiplayer_seating(player_count)?,
+                _ => ask_for_seating(None)?,
+            };
+            multiplayer::play(&seating, variant, board, player_count, options)
+        }
+        Variant::Ultimate => ultimate::play(&ask_for_seating(None)?, options),
+        Variant::Qubic => qubic::play(&ask_for_seating(None)?, options),
+        Variant::Gravity => gravity::play(&ask_for_seating(None)?, board_size, options),
+        Variant::Wild => wild::play(&ask_for_seating(None)?, options),
+        Variant::Morris => morris::play(&ask_for_seating(None)?, options),
+        Variant::Infinite => infinite::play(&ask_for_seating(None)?, options),
+        Variant::Quantum => quantum::play(&ask_for_seating(None)?, options),
+        Variant::OrderChaos => order_chaos::play(&ask_for_seating(None)?, options),
+        Variant::Phantom => phantom::play(&ask_for_seating(None)?, options),
+        Variant::Numerical => numerical::play(&ask_for_seating(None)?, options),
+        Variant::Gobblet => gobblet::play(&ask_for_seating(None)?, options),
+        Variant::Connect6 => connect6::play(&ask_for_seating(None)?, board_size, options),
+        // Custom rules only come from a variant file.
+        Variant::Custom => match &definition {
+            Some(definition) => custom::play(&ask_for_seating(None)?, definition, options),
+            None => Err(InputError::NotAVariant(String::from(variant.name())).into()),
+        },
+        Variant::Gomoku => gomoku::play(
+            &ask_for_seating(None)?,
+            board_size,
+            options.gomoku_rule.unwrap_or_default(),
+            options.opening.unwrap_or_default(),
+            options,
+        ),
+        Variant::Notakto => notakto::play(
+            &ask_for_seating(None)?,
+            options.board_count.unwrap_or(DEFAULT_BOARD_COUNT),
+            options,
+        ),
+    }
+}
+
+fn play_classic(variant: Variant, options: &GameOptions) -> Result<(), Error> {
+    let (board, seating) = match &options.start_position {
+        Some((board, side_to_move)) => (
+            Board::from(board),
+            ask_for_seating(Some(first_player_for(board, side_to_move)))?,
+        ),
+        None => (Board::new(), ask_for_seating(None)?),
+    };
+
+    let mut record = new_record(variant, &seating);
+    if let Some((board, side_to_move)) = &options.start_position {
+        record.metadata.start_position = Some(position_string(board, side_to_move));
+    }
+
+    let mut classic = Classic::new(board, &seating.first_player, variant);
+    next_turn(&mut classic, &seating, &mut record, options)
 }
 
-pub fn next_turn(
-    board: &mut Board,
-    player_character: &Player,
-    first_player: &Player,
-    against_computer: bool,
-) {
-    let player = current_player(&board, first_player);
+// What a front-end needs besides the rules to run a game: how it's drawn, how
+// a human is asked for a move, how the computer picks one, and how moves go
+// to and from the record.
+pub trait Playable: Rules {
+    // Anything shown above whose turn it is, such as who sits where.
+    fn render_heading(&self, _metadata: &GameMetadata) {}
+
+    fn render(&self);
 
-    clear_screen();
+    fn render_outcome(&self, outcome: &Outcome) {
+        match &outcome.result {
+            GameResult::Winner(winner) => render_winning_player(winner),
+            GameResult::Draw | GameResult::Unfinished => render_stalemate(),
+        }
+    }
+
+    // Runs before a human's turn when everyone shares the keyboard, for
+    // games where the players mustn't see each other's view.
+    fn hand_over(&self, _player: &Player) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn ask_for_move(&self) -> Result<Self::Move, Error>;
+
+    // Every move a human makes in one go. That's a single move unless the
+    // turn asks for more than one, like Connect6's pairs of stones.
+    fn ask_for_moves(&self) -> Result<Vec<Self::Move>, Error> {
+        Ok(vec![self.ask_for_move()?])
+    }
+
+    fn computer_move(&self, player: &Player) -> Option<Self::Move>;
+
+    // Called for each move once the turn has been played, so it can note
+    // what it did.
+    fn record_move(&self, record: &mut GameRecord, player: &Player, game_move: Self::Move);
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Self::Move, MoveError>;
+
+    // Who made which moves in one entry of the record. That's almost always
+    // just the entry's player and its move.
+    fn recorded_moves(recorded: &RecordedMove) -> Result<Vec<(Player, Self::Move)>, MoveError> {
+        Ok(vec![(
+            recorded.player.clone(),
+            Self::recorded_move(recorded)?,
+        )])
+    }
+}
+
+pub fn next_turn<G: Playable>(
+    game: &mut G,
+    seating: &Seating,
+    record: &mut GameRecord,
+    options: &GameOptions,
+) -> Result<(), Error> {
+    let player = game.current_player();
+    let over = game.outcome();
+
+    if over.is_none() && !seating.against_computer {
+        game.hand_over(&player)?;
+    }
+
+    clear_screen()?;
     render_empty_lines(1);
+    game.render_heading(&record.metadata);
     render_current_player(&player);
     render_empty_lines(1);
-    render_board(&board);
+    game.render();
     render_empty_lines(1);
 
-    if game_over(board) {
-        match winning_player_on(board) {
-            Some(winner) => render_winning_player(&winner),
-            None => render_stalemate(),
-        }
+    if let Some(outcome) = over {
+        game.render_outcome(&outcome);
+        record.finish(outcome.result, outcome.termination);
 
         render_empty_lines(1);
+        return finish_game(record, options);
+    }
 
-        if confirm("Would you like to play again?", true) {
-            init()
-        }
+    let turn = if seating.is_computer(&player) {
+        computer_turn(game, &player).map(|game_move| vec![game_move])
     } else {
-        if against_computer && &player != player_character {
-            let (_, row_index, col_index) = best_next_move(board, &player).unwrap();
-            board.set_cell_at(row_index, col_index, Cell::Marker(player));
-        } else {
-            take_turn(board, &player);
+        take_turn(game, &player)
+    };
+
+    match turn {
+        Ok(game_moves) => {
+            for game_move in game_moves {
+                game.record_move(record, &player, game_move);
+            }
+            next_turn(game, seating, record, options)
+        }
+        Err(error) => {
+            save_record(record, options);
+            Err(error)
         }
+    }
+}
+
+pub fn finish_game(record: &GameRecord, options: &GameOptions) -> Result<(), Error> {
+    save_record(record, options);
+
+    if confirm("Would you like to play again?", true)? {
+        init(options)?;
+    }
 
-        next_turn(board, player_character, first_player, against_computer);
+    Ok(())
+}
+
+pub fn save_record(record: &GameRecord, options: &GameOptions) {
+    if let Some(path) = &options.export_path {
+        if let Err(error) = append_record(path, record) {
+            println!("Couldn't save the game to {}: {}", path, error);
+        }
     }
 }
 
-pub fn take_turn(board: &mut Board, player: &Player) {
-    let (row_index, col_index) = ask_for_cell_position("Which cell? (e.g., A1, C2, etc.)");
-    let cell = board.get_cell_at(row_index, col_index);
+pub fn computer_turn<G: Playable>(game: &mut G, player: &Player) -> Result<G::Move, Error> {
+    let game_move = game
+        .computer_move(player)
+        .ok_or_else(|| Error::NoMoveAvailable(player.clone()))?;
+    game.play(player, game_move)?;
+    Ok(game_move)
+}
 
-    match cell {
-        Cell::Empty => board.set_cell_at(row_index, col_index, Cell::Marker(player.clone())),
-        Cell::Marker(_) => {
-            println!("Player {} has already taken this spot.", cell.character());
-            take_turn(board, player)
+// Either all of a human's moves go on the board or, if one of them is
+// illegal, none of them do. Any left over once the game is won are dropped.
+pub fn take_turn<G: Playable>(game: &mut G, player: &Player) -> Result<Vec<G::Move>, Error> {
+    with_retries(|| {
+        let mut played = game.clone();
+        let mut game_moves = vec![];
+        for game_move in game.ask_for_moves()? {
+            if played.outcome().is_some() {
+                break;
+            }
+            played.play(player, game_move)?;
+            game_moves.push(game_move);
+        }
+
+        *game = played;
+        Ok(game_moves)
+    })
+}
+
+pub fn replay(path: &str, definitions: &[VariantDefinition]) -> Result<(), Error> {
+    let contents = fs::read_to_string(path)?;
+    let records = parse_all(&contents, RecordFormat::from_path(path))?;
+
+    for record in records {
+        render_empty_lines(1);
+        render_record_summary(&record);
+        render_empty_lines(1);
+
+        match record.metadata.variant {
+            Variant::Ultimate => ultimate::replay(&record),
+            Variant::Qubic => qubic::replay(&record),
+            Variant::Gravity => gravity::replay(&record),
+            Variant::Wild => wild::replay(&record),
+            Variant::Notakto => notakto::replay(&record),
+            Variant::Morris => morris::replay(&record),
+            Variant::Infinite => infinite::replay(&record),
+            Variant::Quantum => quantum::replay(&record),
+            Variant::OrderChaos => order_chaos::replay(&record),
+            Variant::Gomoku => gomoku::replay(&record),
+            Variant::Connect6 => connect6::replay(&record),
+            Variant::Phantom => phantom::replay(&record),
+            Variant::Numerical => numerical::replay(&record),
+            Variant::Gobblet => gobblet::replay(&record),
+            Variant::Custom => custom::replay(&record, definitions),
+            Variant::Multiplayer | Variant::Shaped => multiplayer::replay(&record),
+            Variant::Classic | Variant::Misere => replay_classic(&record),
         }
     }
+
+    Ok(())
+}
+
+fn replay_classic(record: &GameRecord) {
+    let board = match &record.metadata.start_position {
+        Some(position) => match parse_position(position) {
+            Ok((board, _)) => board,
+            Err(error) => return println!("{}", error),
+        },
+        None => Board::new(),
+    };
+    let metadata = &record.metadata;
+    replay_game(
+        &mut Classic::new(board, &metadata.first_player, metadata.variant),
+        record,
+    );
+}
+
+pub fn replay_game<G: Playable>(game: &mut G, record: &GameRecord) {
+    let illegal_move = record.moves.iter().find_map(|recorded| {
+        G::recorded_moves(recorded)
+            .and_then(|moves| {
+                moves
+                    .into_iter()
+                    .try_for_each(|(player, game_move)| game.play(&player, game_move))
+            })
+            .err()
+    });
+
+    game.render();
+
+    if let Some(error) = illegal_move {
+        println!("Replay stopped at an illegal move: {}", error);
+    }
+}
+
+pub fn check(position: &str) -> Result<(), Error> {
+    let (board, side_to_move) = read_position(position)?;
+    render_board(&board);
+    render_empty_lines(1);
+
+    match validate_position(&board, &side_to_move) {
+        Ok(()) => println!("{}", classify_position(&board)),
+        Err(error) => println!("{}", error),
+    }
+
+    Ok(())
 }
@@ -1,9 +1,159 @@
+mod error;
 mod game;
 mod models;
+mod modes;
 mod utils;
 
 extern crate regex;
 
+use error::{Error, InputError};
+use game::GameOptions;
+use models::board::BoardSize;
+use models::gomoku::{GomokuRule, Opening};
+use models::variant::Variant;
+use std::env;
+use std::fs;
+use std::process;
+use utils::definitions::{find_definition, load_definitions, DEFAULT_VARIANTS_DIR};
+use utils::input;
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let option_value = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|index| args.get(index + 1))
+            .map(|value| value.as_str())
+    };
+
+    let (definitions, errors) =
+        load_definitions(option_value("--variants").unwrap_or(DEFAULT_VARIANTS_DIR));
+    for error in errors {
+        eprintln!("Skipping a variant file. {}", error.to_string().trim_end());
+    }
+
+    if let Some(path) = option_value("--replay") {
+        return game::replay(path, &definitions);
+    }
+
+    if let Some(position) = option_value("--check") {
+        return game::check(position);
+    }
+
+    if let Some(path) = option_value("--script") {
+        input::use_script(path)?;
+    }
+
+    if let Some(max_retries) = option_value("--max-retries") {
+        let max_retries = max_retries
+            .parse::<usize>()
+            .map_err(|_| InputError::NotANumber(String::from(max_retries)))?;
+        input::set_max_retries(max_retries);
+    }
+
+    let (variant, definition) = match option_value("--variant") {
+        Some(name) => match (
+            Variant::from_name(name),
+            find_definition(&definitions, name),
+        ) {
+            (Some(variant), _) => (Some(variant), None),
+            (None, Some(definition)) => (Some(Variant::Custom), Some(definition.clone())),
+            (None, None) => return Err(Error::Input(InputError::NotAVariant(String::from(name)))),
+        },
+        None => (None, None),
+    };
+
+    let board_dimensions = match option_value("--size") {
+        Some(size) => Some(
+            BoardSize::parse_dimensions(size)
+                .ok_or_else(|| InputError::NotABoardSize(String::from(size)))?,
+        ),
+        None => None,
+    };
+    let win_length = match option_value("--win-length") {
+        Some(length) => Some(
+            length
+                .parse::<usize>()
+                .map_err(|_| InputError::NotANumber(String::from(length)))?,
+        ),
+        None => None,
+    };
+    let board_count = match option_value("--boards") {
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => Some(count),
+            _ => return Err(Error::Input(InputError::NotANumber(String::from(count)))),
+        },
+        None => None,
+    };
+
+    let player_count = match option_value("--players") {
+        Some(count) => Some(
+            count
+                .parse::<usize>()
+                .map_err(|_| InputError::NotANumber(String::from(count)))?,
+        ),
+        None => None,
+    };
+
+    let shape = match option_value("--shape") {
+        Some(path) => Some(fs::read_to_string(path)?),
+        None => None,
+    };
+    let blocked_cells = match option_value("--blocked") {
+        Some(count) => Some(
+            count
+                .parse::<usize>()
+                .map_err(|_| InputError::NotANumber(String::from(count)))?,
+        ),
+        None => None,
+    };
+
+    let gomoku_rule = match option_value("--rule") {
+        Some(name) => Some(
+            GomokuRule::from_name(name)
+                .ok_or_else(|| InputError::NotAGomokuRule(String::from(name)))?,
+        ),
+        None => None,
+    };
+    let opening = match option_value("--opening") {
+        Some(name) => Some(
+            Opening::from_name(name).ok_or_else(|| InputError::NotAnOpening(String::from(name)))?,
+        ),
+        None => None,
+    };
+
+    let mut options = GameOptions {
+        variant,
+        export_path: option_value("--export").map(String::from),
+        board_dimensions,
+        win_length,
+        board_count,
+        player_count,
+        shape,
+        blocked_cells,
+        toroidal: args.iter().any(|arg| arg == "--torus"),
+        gomoku_rule,
+        opening,
+        definitions,
+        definition,
+        ..GameOptions::default()
+    };
+
+    if let Some(position) = option_value("--position") {
+        options = options.with_start_position(position)?;
+    }
+
+    game::init(&options)
+}
+
 fn main() {
-    game::init();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(()) => {}
+        Err(error) if error.is_end_of_input() => println!("\nBye!"),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
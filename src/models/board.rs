@@ -1,21 +1,203 @@
+use crate::error::MoveError;
 use crate::models::cell::Cell;
 use crate::models::row::Row;
+use crate::utils::lines::{lines, wrapped_lines};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoardSize {
+    pub rows: usize,
+    pub cols: usize,
+    pub win_length: usize,
+}
+
+#[derive(Debug, Clone)]
 pub struct Board {
-    pub rows: [Row; 3],
+    pub rows: Vec<Row>,
+    pub win_length: usize,
+    // Lines run off one edge and back in at the opposite one.
+    pub toroidal: bool,
+}
+
+impl BoardSize {
+    pub const CLASSIC: BoardSize = BoardSize {
+        rows: 3,
+        cols: 3,
+        win_length: 3,
+    };
+
+    pub const CONNECT_FOUR: BoardSize = BoardSize {
+        rows: 6,
+        cols: 7,
+        win_length: 4,
+    };
+
+    pub const ORDER_AND_CHAOS: BoardSize = BoardSize {
+        rows: 6,
+        cols: 6,
+        win_length: 5,
+    };
+
+    pub const MULTIPLAYER: BoardSize = BoardSize {
+        rows: 5,
+        cols: 5,
+        win_length: 4,
+    };
+
+    pub const SHAPED: BoardSize = BoardSize {
+        rows: 5,
+        cols: 5,
+        win_length: 4,
+    };
+
+    pub const CONNECT6: BoardSize = BoardSize {
+        rows: 19,
+        cols: 19,
+        win_length: 6,
+    };
+
+    pub const GOMOKU: BoardSize = BoardSize {
+        rows: 15,
+        cols: 15,
+        win_length: 5,
+    };
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let (dimensions, win_length) = text.trim().split_once('/')?;
+        let (rows, cols) = BoardSize::parse_dimensions(dimensions)?;
+        let size = BoardSize {
+            rows,
+            cols,
+            win_length: win_length.parse().ok()?,
+        };
+        Some(size).filter(BoardSize::is_playable)
+    }
+
+    pub fn parse_dimensions(text: &str) -> Option<(usize, usize)> {
+        let (rows, cols) = text.trim().split_once(['x', 'X'])?;
+        Some((rows.parse().ok()?, cols.parse().ok()?))
+    }
+
+    // Rows are named by a single letter, and a line has to fit on the board.
+    pub fn is_playable(&self) -> bool {
+        (1..=26).contains(&self.rows)
+            && self.cols > 0
+            && self.win_length > 0
+            && self.win_length <= self.rows.max(self.cols)
+    }
+}
+
+impl fmt::Display for BoardSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}/{}", self.rows, self.cols, self.win_length)
+    }
 }
 
 impl Board {
     pub fn new() -> Self {
+        Board::with_size(BoardSize::CLASSIC)
+    }
+
+    pub fn with_size(size: BoardSize) -> Self {
         Self {
-            rows: [Row::new(), Row::new(), Row::new()],
+            rows: vec![Row::new(size.cols); size.rows],
+            win_length: size.win_length,
+            toroidal: false,
+        }
+    }
+
+    // A board drawn as text, one line per row: '.' is a cell and '#' or a
+    // space is a hole. Short rows are padded with holes, so a cross or a
+    // diamond only needs its cells drawn. Rows can also be separated by '/'.
+    pub fn from_mask(mask: &str, win_length: usize) -> Option<Self> {
+        let lines: Vec<&str> = mask
+            .split(['\n', '/'])
+            .map(|line| line.trim_end())
+            .skip_while(|line| line.is_empty())
+            .collect();
+        let row_count = lines.iter().rposition(|line| !line.is_empty())? + 1;
+        let col_count = lines.iter().map(|line| line.chars().count()).max()?;
+
+        let mut board = Board::with_size(BoardSize {
+            rows: row_count,
+            cols: col_count,
+            win_length,
+        });
+        for (row_index, line) in lines[..row_count].iter().enumerate() {
+            let mut characters = line.chars();
+            for cell in board.rows[row_index].cells.iter_mut() {
+                *cell = match characters.next() {
+                    Some('.') => Cell::Empty,
+                    Some('#') | Some(' ') | None => Cell::Blocked,
+                    Some(_) => return None,
+                };
+            }
+        }
+
+        Some(board).filter(|board| board.size().is_playable() && board.open_cell_count() > 0)
+    }
+
+    // The board's shape in the form `from_mask` reads, on one line.
+    pub fn mask(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Blocked => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+
+    pub fn has_blocked_cells(&self) -> bool {
+        self.get_cells().iter().any(|cell| **cell == Cell::Blocked)
+    }
+
+    pub fn open_cell_count(&self) -> usize {
+        self.get_cells()
+            .iter()
+            .filter(|cell| ***cell != Cell::Blocked)
+            .count()
+    }
+
+    // Blocks `count` of the empty cells, picked with a xorshift generator so
+    // the same seed always gives the same board.
+    pub fn block_random_cells(&mut self, count: usize, seed: u64) {
+        let mut state = seed | 1;
+        for _ in 0..count {
+            let empty: Vec<(usize, usize)> = self
+                .get_cell_positions()
+                .iter()
+                .filter(|(cell, _, _)| **cell == Cell::Empty)
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect();
+            if empty.is_empty() {
+                return;
+            }
+
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let (row_index, col_index) = empty[(state % empty.len() as u64) as usize];
+            self.rows[row_index].cells[col_index] = Cell::Blocked;
         }
     }
 
     pub fn from(board: &Board) -> Self {
-        Self {
-            rows: board.rows.clone(),
+        board.clone()
+    }
+
+    pub fn size(&self) -> BoardSize {
+        BoardSize {
+            rows: self.rows.len(),
+            cols: self.rows.first().map_or(0, |row| row.cells.len()),
+            win_length: self.win_length,
         }
     }
 
@@ -47,42 +229,45 @@ impl Board {
     }
 
     pub fn get_cell_position_rows(&self) -> Vec<Vec<(&Cell, usize, usize)>> {
-        self.get_cell_positions().iter().fold(
-            vec![vec![(&Cell::Empty, 0, 0); 3]; 3],
-            |mut rows, (cell, row_index, col_index)| {
-                rows[*row_index][*col_index] = (cell, *row_index, *col_index);
-                rows
-            },
-        )
-    }
-
-    pub fn get_cell_position_cols(&self) -> Vec<Vec<(&Cell, usize, usize)>> {
-        self.get_cell_positions().iter().fold(
-            vec![vec![(&Cell::Empty, 0, 0); 3]; 3],
-            |mut cols, (cell, row_index, col_index)| {
-                cols[*col_index][*row_index] = (cell, *row_index, *col_index);
-                cols
-            },
-        )
-    }
-
-    pub fn get_cell_position_crosses(&self) -> Vec<Vec<(&Cell, usize, usize)>> {
-        vec![
-            vec![
-                (&self.rows[0].cells[0], 0, 0),
-                (&self.rows[1].cells[1], 1, 1),
-                (&self.rows[2].cells[2], 2, 2),
-            ],
-            vec![
-                (&self.rows[0].cells[2], 0, 2),
-                (&self.rows[1].cells[1], 1, 1),
-                (&self.rows[2].cells[0], 2, 0),
-            ],
-        ]
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                row.cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col_index, cell)| (cell, row_index, col_index))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Lines through a blocked cell can never be completed, so they're left out.
+    pub fn get_cell_position_lines(&self) -> Vec<Vec<(&Cell, usize, usize)>> {
+        let size = self.size();
+        let dimensions = [size.rows, size.cols];
+        let coordinates = if self.toroidal {
+            wrapped_lines(&dimensions, size.win_length)
+        } else {
+            lines(&dimensions, size.win_length)
+        };
+        coordinates
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|coordinates| {
+                        let (row_index, col_index) = (coordinates[0], coordinates[1]);
+                        (&self.rows[row_index].cells[col_index], row_index, col_index)
+                    })
+                    .collect::<Vec<(&Cell, usize, usize)>>()
+            })
+            .filter(|line| line.iter().all(|(cell, _, _)| **cell != Cell::Blocked))
+            .collect()
     }
 
     pub fn get_center_cell_position(&self) -> (&Cell, usize, usize) {
-        (&self.rows[1].cells[1], 1, 1)
+        let (row_index, col_index) = (self.rows.len() / 2, self.size().cols / 2);
+        (&self.rows[row_index].cells[col_index], row_index, col_index)
     }
 
     pub fn get_corner_cell_positions(&self) -> Vec<(&Cell, usize, usize)> {
@@ -125,11 +310,105 @@ impl Board {
         ]
     }
 
-    pub fn get_cell_at(&self, row_index: usize, col_index: usize) -> &Cell {
-        &self.rows[row_index].cells[col_index]
+    // Cells joined to this one by a line drawn on the board: the orthogonal
+    // neighbors, plus diagonal ones when both cells sit on a main diagonal.
+    pub fn adjacent_positions(&self, row_index: usize, col_index: usize) -> Vec<(usize, usize)> {
+        let size = self.size();
+        let on_diagonal = |row: usize, col: usize| row == col && size.rows == size.cols;
+        let on_anti_diagonal =
+            |row: usize, col: usize| row + col + 1 == size.cols && size.rows == size.cols;
+
+        (-1isize..=1)
+            .flat_map(|row_step| (-1isize..=1).map(move |col_step| (row_step, col_step)))
+            .filter(|&step| step != (0, 0))
+            .filter_map(|(row_step, col_step)| {
+                let row = row_index
+                    .checked_add_signed(row_step)
+                    .filter(|&row| row < size.rows)?;
+                let col = col_index
+                    .checked_add_signed(col_step)
+                    .filter(|&col| col < size.cols)?;
+                let diagonal_step = row_step != 0 && col_step != 0;
+                let drawn = !diagonal_step
+                    || (on_diagonal(row_index, col_index) && on_diagonal(row, col))
+                    || (on_anti_diagonal(row_index, col_index) && on_anti_diagonal(row, col));
+                Some((row, col)).filter(|_| drawn)
+            })
+            .collect()
+    }
+
+    // Where a marker dropped into this column comes to rest, if it fits.
+    pub fn lowest_empty_row(&self, col_index: usize) -> Option<usize> {
+        self.rows
+            .iter()
+            .rposition(|row| row.cells.get(col_index) == Some(&Cell::Empty))
+    }
+
+    pub fn get_cell_at(&self, row_index: usize, col_index: usize) -> Result<&Cell, MoveError> {
+        self.rows
+            .get(row_index)
+            .and_then(|row| row.cells.get(col_index))
+            .ok_or(MoveError::OutOfBounds {
+                row_index,
+                col_index,
+            })
     }
 
-    pub fn set_cell_at(&mut self, row_index: usize, col_index: usize, cell: Cell) {
-        self.rows[row_index].cells[col_index] = cell;
+    pub fn set_cell_at(
+        &mut self,
+        row_index: usize,
+        col_index: usize,
+        cell: Cell,
+    ) -> Result<(), MoveError> {
+        let slot = self
+            .rows
+            .get_mut(row_index)
+            .and_then(|row| row.cells.get_mut(col_index))
+            .ok_or(MoveError::OutOfBounds {
+                row_index,
+                col_index,
+            })?;
+        *slot = cell;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_cross_only_has_lines_along_its_arms() {
+        let cross = Board::from_mask("  .\n  .\n.....\n  .\n  .", 3).unwrap();
+        assert_eq!(cross.size().rows, 5);
+        assert_eq!(cross.size().cols, 5);
+        assert_eq!(cross.open_cell_count(), 9);
+        assert_eq!(cross.mask(), "##.##/##.##/...../##.##/##.##");
+        // Three along the middle row and three down the middle column.
+        assert_eq!(cross.get_cell_position_lines().len(), 6);
+    }
+
+    #[test]
+    fn masks_only_hold_cells_and_holes() {
+        assert!(Board::from_mask("..x/...", 3).is_none());
+        assert!(Board::from_mask("###/###", 3).is_none());
+        assert!(Board::from_mask(".#./...", 3).is_some());
+    }
+
+    #[test]
+    fn no_line_runs_through_a_blocked_cell() {
+        let mut board = Board::with_size(BoardSize {
+            rows: 5,
+            cols: 5,
+            win_length: 3,
+        });
+        board.block_random_cells(4, 42);
+
+        assert_eq!(board.open_cell_count(), 21);
+        let lines = board.get_cell_position_lines();
+        assert!(!lines.is_empty());
+        assert!(lines
+            .iter()
+            .all(|line| line.iter().all(|(cell, _, _)| **cell != Cell::Blocked)));
     }
 }
@@ -1,16 +1,28 @@
 use crate::models::player::Player;
+use crate::models::symbol::Symbol;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Cell {
     Marker(Player),
+    // A mark that belongs to nobody, whoever placed it.
+    Symbol(Symbol),
     Empty,
+    // Not part of the board: nobody can play here and no line runs through.
+    Blocked,
+    // A number rather than a player's mark, as in numerical tic-tac-toe.
+    Number(u8),
 }
 
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
 impl Cell {
     pub fn character(&self) -> &str {
         match self {
             Cell::Marker(player) => player.character(),
+            Cell::Symbol(symbol) => symbol.character(),
             Cell::Empty => " ",
+            Cell::Blocked => "#",
+            Cell::Number(number) => DIGITS.get(*number as usize).copied().unwrap_or("?"),
         }
     }
 }
@@ -0,0 +1,73 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, current_player, winner_on};
+use crate::utils::validation::play_move;
+
+pub type Position = (usize, usize);
+
+// Plain tic-tac-toe, or misère when the line loses instead, on the classic
+// board. This is the reference for how a variant implements `Rules`.
+#[derive(Debug, Clone)]
+pub struct Classic {
+    pub board: Board,
+    pub first_player: Player,
+    variant: Variant,
+}
+
+impl Classic {
+    pub fn new(board: Board, first_player: &Player, variant: Variant) -> Self {
+        Self {
+            board,
+            first_player: first_player.clone(),
+            variant,
+        }
+    }
+}
+
+impl Rules for Classic {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    fn current_player(&self) -> Player {
+        current_player(&self.board, &self.first_player)
+    }
+
+    fn legal_moves(&self) -> Vec<Position> {
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, (row_index, col_index): Position) -> Result<(), MoveError> {
+        play_move(
+            &mut self.board,
+            &self.first_player,
+            player,
+            row_index,
+            col_index,
+        )
+    }
+
+    fn apply(&mut self, player: &Player, (row_index, col_index): Position) {
+        self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        match winner_on(&self.board, self.variant) {
+            Some(winner) => Some(Outcome::win(winner, Termination::ThreeInARow)),
+            None if board_full(&self.board) => Some(Outcome::draw(Termination::BoardFull)),
+            None => None,
+        }
+    }
+}
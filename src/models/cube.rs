@@ -0,0 +1,205 @@
+use crate::error::MoveError;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::lines::lines;
+use crate::utils::qubic_ai::{candidate_moves, evaluate};
+
+pub const CUBE_SIZE: usize = 4;
+
+pub type CubePosition = (usize, usize, usize);
+
+#[derive(Debug, Clone)]
+pub struct Cube {
+    pub size: usize,
+    pub cells: Vec<Cell>,
+    pub lines: Vec<Vec<usize>>,
+    pub lines_through: Vec<Vec<usize>>,
+    pub first_player: Player,
+}
+
+impl Cube {
+    pub fn new(size: usize, first_player: &Player) -> Self {
+        let lines: Vec<Vec<usize>> = lines(&[size, size, size], size)
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|coordinates| {
+                        (coordinates[0] * size + coordinates[1]) * size + coordinates[2]
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut lines_through = vec![vec![]; size * size * size];
+        for (line_index, line) in lines.iter().enumerate() {
+            for &index in line {
+                lines_through[index].push(line_index);
+            }
+        }
+
+        Self {
+            size,
+            cells: vec![Cell::Empty; size * size * size],
+            lines,
+            lines_through,
+            first_player: first_player.clone(),
+        }
+    }
+
+    pub fn index_of(&self, position: CubePosition) -> Result<usize, MoveError> {
+        let (layer_index, row_index, col_index) = position;
+        if layer_index < self.size && row_index < self.size && col_index < self.size {
+            Ok((layer_index * self.size + row_index) * self.size + col_index)
+        } else {
+            Err(MoveError::OutOfCube(position))
+        }
+    }
+
+    pub fn position_of(&self, index: usize) -> CubePosition {
+        (
+            index / (self.size * self.size),
+            index / self.size % self.size,
+            index % self.size,
+        )
+    }
+
+    pub fn get_cell_at(&self, position: CubePosition) -> Result<&Cell, MoveError> {
+        Ok(&self.cells[self.index_of(position)?])
+    }
+
+    pub fn set_cell_at(&mut self, position: CubePosition, cell: Cell) -> Result<(), MoveError> {
+        let index = self.index_of(position)?;
+        self.cells[index] = cell;
+        Ok(())
+    }
+
+    pub fn layer_rows(&self, layer_index: usize) -> Vec<&[Cell]> {
+        let layer_start = layer_index * self.size * self.size;
+        self.cells[layer_start..layer_start + self.size * self.size]
+            .chunks(self.size)
+            .collect()
+    }
+
+    pub fn winning_player(&self) -> Option<Player> {
+        self.lines
+            .iter()
+            .find_map(|line| match &self.cells[line[0]] {
+                Cell::Marker(player)
+                    if line
+                        .iter()
+                        .all(|&index| self.cells[index] == self.cells[line[0]]) =>
+                {
+                    Some(player.clone())
+                }
+                _ => None,
+            })
+    }
+
+    pub fn full(&self) -> bool {
+        self.cells.iter().all(|cell| *cell != Cell::Empty)
+    }
+}
+
+impl Rules for Cube {
+    type Move = CubePosition;
+
+    fn variant(&self) -> Variant {
+        Variant::Qubic
+    }
+
+    fn current_player(&self) -> Player {
+        let count = |player: &Player| {
+            self.cells
+                .iter()
+                .filter(|cell| **cell == Cell::Marker(player.clone()))
+                .count()
+        };
+
+        if count(&self.first_player) > count(&self.first_player.opponent()) {
+            self.first_player.opponent()
+        } else {
+            self.first_player.clone()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<CubePosition> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| **cell == Cell::Empty)
+            .map(|(index, _)| self.position_of(index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, position: CubePosition) -> Result<(), MoveError> {
+        let cell = self.get_cell_at(position)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        if let Cell::Marker(by) = cell {
+            return Err(MoveError::OccupiedInCube {
+                position,
+                by: by.clone(),
+            });
+        }
+
+        self.set_cell_at(position, Cell::Marker(player.clone()))
+    }
+
+    fn apply(&mut self, player: &Player, position: CubePosition) {
+        let (layer_index, row_index, col_index) = position;
+        self.cells[(layer_index * self.size + row_index) * self.size + col_index] =
+            Cell::Marker(player.clone());
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        match self.winning_player() {
+            Some(winner) => Some(Outcome::win(winner, Termination::LineCompleted)),
+            None if self.full() => Some(Outcome::draw(Termination::BoardFull)),
+            None => None,
+        }
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        evaluate(self, player)
+    }
+
+    // The full 64-cell tree is far too big to walk, so only the most
+    // promising few cells get searched.
+    fn candidate_moves(&self) -> Vec<CubePosition> {
+        candidate_moves(self, &self.current_player())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_four_cube_has_seventy_six_lines() {
+        let cube = Cube::new(CUBE_SIZE, &Player::X);
+        assert_eq!(cube.lines.len(), 76);
+        assert!(cube.lines.iter().all(|line| line.len() == CUBE_SIZE));
+
+        // Corners and the inner cells sit on seven lines, the rest on four.
+        let corner = cube.index_of((0, 0, 0)).unwrap();
+        let inner = cube.index_of((1, 1, 1)).unwrap();
+        let edge = cube.index_of((0, 0, 1)).unwrap();
+        assert_eq!(cube.lines_through[corner].len(), 7);
+        assert_eq!(cube.lines_through[inner].len(), 7);
+        assert_eq!(cube.lines_through[edge].len(), 4);
+    }
+}
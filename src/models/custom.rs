@@ -0,0 +1,174 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::definition::{Direction, VariantDefinition};
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use crate::utils::ai::same_player_in;
+use crate::utils::validation::check_empty;
+use crate::utils::validation::count_markers;
+
+pub type Position = (usize, usize);
+
+// A game played by the rules in a variant file. The definition is checked
+// when it's loaded, so anything it allows can be played here.
+#[derive(Debug, Clone)]
+pub struct Custom<'a> {
+    pub definition: &'a VariantDefinition,
+    pub board: Board,
+    pub first_player: Player,
+    schedule: TurnSchedule,
+    marks_placed: usize,
+}
+
+impl<'a> Custom<'a> {
+    pub fn new(definition: &'a VariantDefinition, first_player: &Player) -> Self {
+        Self {
+            definition,
+            board: definition.board().unwrap_or_else(Board::new),
+            first_player: first_player.clone(),
+            schedule: definition.schedule().unwrap_or_default(),
+            marks_placed: 0,
+        }
+    }
+
+    pub fn schedule(&self) -> TurnSchedule {
+        self.schedule
+    }
+
+    pub fn marks_left_in_turn(&self) -> usize {
+        self.schedule.marks_left_in_turn(self.marks_placed)
+    }
+
+    pub fn pieces_left(&self, player: &Player) -> Option<usize> {
+        self.definition
+            .pieces
+            .map(|pieces| pieces.saturating_sub(count_markers(&self.board, player)))
+    }
+
+    // The board's lines, less the ones running in directions that don't count.
+    fn lines(&self) -> Vec<Vec<(&Cell, usize, usize)>> {
+        self.board
+            .get_cell_position_lines()
+            .into_iter()
+            .filter(|line| match line.as_slice() {
+                [(_, from_row, from_col), (_, to_row, to_col), ..] => {
+                    Direction::between((*from_row, *from_col), (*to_row, *to_col))
+                        .is_some_and(|direction| self.definition.directions.contains(&direction))
+                }
+                _ => true,
+            })
+            .collect()
+    }
+
+    // Whoever filled a line, and the line itself.
+    pub fn completed_line(&self) -> Option<(Player, Vec<Position>)> {
+        self.lines().iter().find_map(|line| {
+            let cells = Board::cell_positions_to_cells(line);
+            let player = same_player_in(&cells)?;
+            let positions = line
+                .iter()
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect();
+            Some((player, positions))
+        })
+    }
+
+    // With gravity a mark falls to the lowest empty cell of its column.
+    pub fn landing_cell(&self, col_index: usize) -> Option<Position> {
+        self.board
+            .lowest_empty_row(col_index)
+            .map(|row_index| (row_index, col_index))
+    }
+}
+
+impl Rules for Custom<'_> {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        Variant::Custom
+    }
+
+    fn current_player(&self) -> Player {
+        self.schedule
+            .mover(self.marks_placed, &self.first_player, 2)
+    }
+
+    // Cells nearer the middle come first, which helps the search cut off
+    // sooner.
+    fn legal_moves(&self) -> Vec<Position> {
+        let mut moves: Vec<Position> = if self.definition.gravity {
+            (0..self.board.size().cols)
+                .filter_map(|col_index| self.landing_cell(col_index))
+                .collect()
+        } else {
+            self.board
+                .get_cell_positions()
+                .iter()
+                .filter(|(cell, _, _)| **cell == Cell::Empty)
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect()
+        };
+
+        let size = self.board.size();
+        moves.sort_by_key(|&(row_index, col_index)| {
+            (row_index * 2).abs_diff(size.rows - 1) + (col_index * 2).abs_diff(size.cols - 1)
+        });
+        moves
+    }
+
+    fn play(&mut self, player: &Player, (row_index, col_index): Position) -> Result<(), MoveError> {
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        check_empty(cell, row_index, col_index)?;
+
+        if self.definition.gravity && self.landing_cell(col_index) != Some((row_index, col_index)) {
+            return Err(MoveError::Floating((row_index, col_index)));
+        }
+
+        self.apply(player, (row_index, col_index));
+        Ok(())
+    }
+
+    fn apply(&mut self, player: &Player, (row_index, col_index): Position) {
+        self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+        self.marks_placed += 1;
+    }
+
+    // In misère the line loses for whoever made it. A player with no pieces
+    // left to place can't move, so the game is drawn.
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some((player, _)) = self.completed_line() {
+            let winner = if self.definition.misere {
+                player.opponent()
+            } else {
+                player
+            };
+            return Some(Outcome::win(winner, Termination::LineCompleted));
+        }
+
+        if self.legal_moves().is_empty() {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+
+        match self.pieces_left(&self.current_player()) {
+            Some(0) => Some(Outcome::draw(Termination::NoMovesLeft)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::models::board::{Board, BoardSize};
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Which way a line runs across the board.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+    // Top left to bottom right.
+    Diagonal,
+    // Top right to bottom left.
+    AntiDiagonal,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::Diagonal,
+        Direction::AntiDiagonal,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Direction::Horizontal => "horizontal",
+            Direction::Vertical => "vertical",
+            Direction::Diagonal => "diagonal",
+            Direction::AntiDiagonal => "anti-diagonal",
+        }
+    }
+
+    // The direction from one cell of a line to the next.
+    pub fn between(from: (usize, usize), to: (usize, usize)) -> Option<Self> {
+        let row_step = to.0 as isize - from.0 as isize;
+        let col_step = to.1 as isize - from.1 as isize;
+        match (row_step, col_step) {
+            (0, 1) => Some(Direction::Horizontal),
+            (1, 0) => Some(Direction::Vertical),
+            (1, 1) => Some(Direction::Diagonal),
+            (1, -1) => Some(Direction::AntiDiagonal),
+            _ => None,
+        }
+    }
+}
+
+fn all_directions() -> Vec<Direction> {
+    Direction::ALL.to_vec()
+}
+
+fn classic_win_length() -> usize {
+    BoardSize::CLASSIC.win_length
+}
+
+// A variant written down as data rather than code, read from a TOML or JSON
+// file. Anything left out plays like classic tic-tac-toe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VariantDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub rows: Option<usize>,
+    #[serde(default)]
+    pub cols: Option<usize>,
+    // Drawn the way `--shape` reads it; when given, it sets the board's size
+    // and `rows` and `cols` are left out.
+    #[serde(default)]
+    pub mask: Option<String>,
+    #[serde(default = "classic_win_length")]
+    pub win_length: usize,
+    #[serde(default = "all_directions")]
+    pub directions: Vec<Direction>,
+    #[serde(default)]
+    pub gravity: bool,
+    #[serde(default)]
+    pub misere: bool,
+    // How many marks each player has. Once the player to move has none
+    // left, the game is drawn.
+    #[serde(default)]
+    pub pieces: Option<usize>,
+    // Written like "1/2", as for Connect6.
+    #[serde(default)]
+    pub schedule: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DefinitionProblem {
+    MissingName,
+    NameTaken(String),
+    NameHasSpaces(String),
+    SizeWithMask,
+    UnplayableSize(BoardSize),
+    NotAMask,
+    NoDirections,
+    RepeatedDirection(Direction),
+    GravityWithMask,
+    NoPieces,
+    NotASchedule(String),
+}
+
+impl fmt::Display for DefinitionProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DefinitionProblem::MissingName => write!(f, "it needs a name"),
+            DefinitionProblem::NameTaken(name) => {
+                write!(f, "'{}' is already the name of another variant", name)
+            }
+            DefinitionProblem::NameHasSpaces(name) => write!(
+                f,
+                "'{}' has spaces in it; use dashes so it works with --variant",
+                name
+            ),
+            DefinitionProblem::SizeWithMask => {
+                write!(f, "give either rows and cols or a mask, not both")
+            }
+            DefinitionProblem::UnplayableSize(size) => write!(
+                f,
+                "a {} board can't be played (1 to 26 rows, and a line has to fit)",
+                size
+            ),
+            DefinitionProblem::NotAMask => write!(
+                f,
+                "the mask should only use '.' for cells and '#' or spaces for holes, \
+                 and a line has to fit on it"
+            ),
+            DefinitionProblem::NoDirections => write!(f, "lines need at least one direction"),
+            DefinitionProblem::RepeatedDirection(direction) => {
+                write!(
+                    f,
+                    "{} is listed more than once in directions",
+                    direction.name()
+                )
+            }
+            DefinitionProblem::GravityWithMask => {
+                write!(f, "gravity only works on a plain board, without a mask")
+            }
+            DefinitionProblem::NoPieces => write!(f, "pieces has to be at least 1"),
+            DefinitionProblem::NotASchedule(schedule) => write!(
+                f,
+                "'{}' isn't a turn schedule (expected something like 1/2)",
+                schedule
+            ),
+        }
+    }
+}
+
+impl VariantDefinition {
+    pub fn description(&self) -> String {
+        if self.description.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, self.description)
+        }
+    }
+
+    fn size(&self) -> BoardSize {
+        BoardSize {
+            rows: self.rows.unwrap_or(BoardSize::CLASSIC.rows),
+            cols: self.cols.unwrap_or(BoardSize::CLASSIC.cols),
+            win_length: self.win_length,
+        }
+    }
+
+    pub fn board(&self) -> Option<Board> {
+        match &self.mask {
+            Some(mask) => Board::from_mask(mask, self.win_length),
+            None => Some(Board::with_size(self.size())),
+        }
+    }
+
+    pub fn schedule(&self) -> Option<TurnSchedule> {
+        match &self.schedule {
+            Some(schedule) => TurnSchedule::parse(schedule),
+            None => Some(TurnSchedule::ALTERNATING),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), DefinitionProblem> {
+        let name = &self.name;
+        if name.trim().is_empty() {
+            return Err(DefinitionProblem::MissingName);
+        }
+        if name.contains(char::is_whitespace) {
+            return Err(DefinitionProblem::NameHasSpaces(self.name.clone()));
+        }
+        if Variant::from_name(name).is_some() || Variant::Custom.name().eq_ignore_ascii_case(name) {
+            return Err(DefinitionProblem::NameTaken(self.name.clone()));
+        }
+
+        if self.mask.is_some() {
+            if self.rows.is_some() || self.cols.is_some() {
+                return Err(DefinitionProblem::SizeWithMask);
+            }
+            if self.board().is_none() {
+                return Err(DefinitionProblem::NotAMask);
+            }
+            if self.gravity {
+                return Err(DefinitionProblem::GravityWithMask);
+            }
+        } else if !self.size().is_playable() {
+            return Err(DefinitionProblem::UnplayableSize(self.size()));
+        }
+
+        if self.directions.is_empty() {
+            return Err(DefinitionProblem::NoDirections);
+        }
+        for (index, direction) in self.directions.iter().enumerate() {
+            if self.directions[..index].contains(direction) {
+                return Err(DefinitionProblem::RepeatedDirection(*direction));
+            }
+        }
+
+        if self.pieces == Some(0) {
+            return Err(DefinitionProblem::NoPieces);
+        }
+        if let (Some(schedule), None) = (&self.schedule, self.schedule()) {
+            return Err(DefinitionProblem::NotASchedule(schedule.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(name: &str) -> VariantDefinition {
+        VariantDefinition {
+            name: String::from(name),
+            description: String::new(),
+            rows: None,
+            cols: None,
+            mask: None,
+            win_length: classic_win_length(),
+            directions: all_directions(),
+            gravity: false,
+            misere: false,
+            pieces: None,
+            schedule: None,
+        }
+    }
+
+    fn problem(definition: VariantDefinition) -> String {
+        definition.validate().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn accepts_a_plain_definition() {
+        assert_eq!(definition("plain").validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bad_names() {
+        assert_eq!(problem(definition(" ")), "it needs a name");
+        assert_eq!(
+            problem(definition("two words")),
+            "'two words' has spaces in it; use dashes so it works with --variant"
+        );
+        assert_eq!(
+            problem(definition("Gomoku")),
+            "'Gomoku' is already the name of another variant"
+        );
+        assert_eq!(
+            definition("custom").validate(),
+            Err(DefinitionProblem::NameTaken(String::from("custom")))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_boards() {
+        let mut both = definition("both");
+        both.rows = Some(4);
+        both.mask = Some(String::from("...\n...\n..."));
+        assert_eq!(
+            problem(both),
+            "give either rows and cols or a mask, not both"
+        );
+
+        let mut too_big = definition("too-big");
+        too_big.rows = Some(27);
+        assert_eq!(
+            problem(too_big),
+            "a 27x3/3 board can't be played (1 to 26 rows, and a line has to fit)"
+        );
+
+        let mut not_a_mask = definition("not-a-mask");
+        not_a_mask.mask = Some(String::from("..x\n...\n..."));
+        assert_eq!(not_a_mask.validate(), Err(DefinitionProblem::NotAMask));
+
+        let mut falling = definition("falling");
+        falling.mask = Some(String::from("...\n...\n..."));
+        falling.gravity = true;
+        assert_eq!(
+            problem(falling),
+            "gravity only works on a plain board, without a mask"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_directions_pieces_and_schedules() {
+        let mut no_directions = definition("no-directions");
+        no_directions.directions = vec![];
+        assert_eq!(problem(no_directions), "lines need at least one direction");
+
+        let mut repeated = definition("repeated");
+        repeated.directions = vec![
+            Direction::Vertical,
+            Direction::Diagonal,
+            Direction::Vertical,
+        ];
+        assert_eq!(
+            problem(repeated),
+            "vertical is listed more than once in directions"
+        );
+
+        let mut no_pieces = definition("no-pieces");
+        no_pieces.pieces = Some(0);
+        assert_eq!(problem(no_pieces), "pieces has to be at least 1");
+
+        let mut schedule = definition("schedule");
+        schedule.schedule = Some(String::from("two"));
+        assert_eq!(
+            problem(schedule),
+            "'two' isn't a turn schedule (expected something like 1/2)"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use crate::models::board::BoardSize;
+use crate::models::gobblet::PieceSize;
+use crate::models::gomoku::{GomokuRule, Opening};
+use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_STRATEGY: &str = "heuristic";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Participant {
+    Human,
+    Computer { strategy: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameResult {
+    Winner(Player),
+    Draw,
+    Unfinished,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Termination {
+    ThreeInARow,
+    LineCompleted,
+    BoardFull,
+    AllBoardsDead,
+    Repetition,
+    NoMovesLeft,
+    MoveLimit,
+    Abandoned,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMove {
+    pub player: Player,
+    // The cube layer in Qubic, or which board in Notakto.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer_index: Option<usize>,
+    pub row_index: usize,
+    pub col_index: usize,
+    // Set when the mover chose which symbol to place rather than their own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<Symbol>,
+    // Where the piece came from, for moves that slide one already down.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<(usize, usize)>,
+    // The second cell of a spooky mark, and where the cycle it closed made
+    // it land, in quantum games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<(usize, usize)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapse: Option<(usize, usize)>,
+    // The number placed, in numerical games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<u8>,
+    // The size of the piece placed from the reserve, in Gobblet games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<PieceSize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameMetadata {
+    #[serde(default)]
+    pub variant: Variant,
+    pub x: Participant,
+    pub o: Participant,
+    // Seats after X and O, in turn order, when more than two play.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub others: Vec<Participant>,
+    pub first_player: Player,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_size: Option<BoardSize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_count: Option<usize>,
+    // The board's mask, when it has holes in it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub toroidal: bool,
+    // How five in a row is judged and how the game opened, in Gomoku.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<GomokuRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opening: Option<Opening>,
+    // Set when turns place more than one mark, as in Connect6.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<TurnSchedule>,
+    // Which variant file's rules were played, for a custom variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    pub date: String,
+    pub result: GameResult,
+    pub termination: Termination,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub metadata: GameMetadata,
+    pub moves: Vec<RecordedMove>,
+}
+
+impl Participant {
+    pub fn computer(strategy: &str) -> Self {
+        Participant::Computer {
+            strategy: String::from(strategy),
+        }
+    }
+}
+
+impl Termination {
+    pub fn description(&self) -> &str {
+        match self {
+            Termination::ThreeInARow => "three in a row",
+            Termination::LineCompleted => "line completed",
+            Termination::BoardFull => "board full",
+            Termination::AllBoardsDead => "all boards dead",
+            Termination::Repetition => "repetition",
+            Termination::NoMovesLeft => "no moves left",
+            Termination::MoveLimit => "move limit",
+            Termination::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn from_description(description: &str) -> Option<Self> {
+        match description {
+            "three in a row" => Some(Termination::ThreeInARow),
+            "line completed" => Some(Termination::LineCompleted),
+            "board full" => Some(Termination::BoardFull),
+            "all boards dead" => Some(Termination::AllBoardsDead),
+            "repetition" => Some(Termination::Repetition),
+            "no moves left" => Some(Termination::NoMovesLeft),
+            "move limit" => Some(Termination::MoveLimit),
+            "abandoned" => Some(Termination::Abandoned),
+            _ => None,
+        }
+    }
+}
+
+impl GameMetadata {
+    pub fn player_count(&self) -> usize {
+        2 + self.others.len()
+    }
+
+    pub fn participant(&self, player: &Player) -> &Participant {
+        match player {
+            Player::X => &self.x,
+            Player::O => &self.o,
+            _ => &self.others[player.index() - 2],
+        }
+    }
+}
+
+impl GameRecord {
+    pub fn new(variant: Variant, x: Participant, o: Participant, first_player: &Player) -> Self {
+        Self {
+            metadata: GameMetadata {
+                variant,
+                x,
+                o,
+                others: vec![],
+                first_player: first_player.clone(),
+                start_position: None,
+                board_size: None,
+                board_count: None,
+                shape: None,
+                toroidal: false,
+                rule: None,
+                opening: None,
+                schedule: None,
+                definition: None,
+                date: current_date(),
+                result: GameResult::Unfinished,
+                termination: Termination::Abandoned,
+            },
+            moves: vec![],
+        }
+    }
+
+    pub fn push_move(&mut self, player: &Player, row_index: usize, col_index: usize) {
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index,
+            col_index,
+            symbol: None,
+            from: None,
+            pair: None,
+            collapse: None,
+            number: None,
+            size: None,
+        });
+    }
+
+    pub fn push_cube_move(&mut self, player: &Player, position: (usize, usize, usize)) {
+        let (layer_index, row_index, col_index) = position;
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: Some(layer_index),
+            row_index,
+            col_index,
+            symbol: None,
+            from: None,
+            pair: None,
+            collapse: None,
+            number: None,
+            size: None,
+        });
+    }
+
+    pub fn push_wild_move(
+        &mut self,
+        player: &Player,
+        symbol: Symbol,
+        row_index: usize,
+        col_index: usize,
+    ) {
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index,
+            col_index,
+            symbol: Some(symbol),
+            from: None,
+            pair: None,
+            collapse: None,
+            number: None,
+            size: None,
+        });
+    }
+
+    pub fn push_slide(&mut self, player: &Player, from: (usize, usize), to: (usize, usize)) {
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index: to.0,
+            col_index: to.1,
+            symbol: None,
+            from: Some(from),
+            pair: None,
+            collapse: None,
+            number: None,
+            size: None,
+        });
+    }
+
+    pub fn push_sized_move(&mut self, player: &Player, size: PieceSize, to: (usize, usize)) {
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index: to.0,
+            col_index: to.1,
+            symbol: None,
+            from: None,
+            pair: None,
+            collapse: None,
+            number: None,
+            size: Some(size),
+        });
+    }
+
+    pub fn push_spooky_move(&mut self, player: &Player, cells: ((usize, usize), (usize, usize))) {
+        let (first, second) = cells;
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index: first.0,
+            col_index: first.1,
+            symbol: None,
+            from: None,
+            pair: Some(second),
+            collapse: None,
+            number: None,
+            size: None,
+        });
+    }
+
+    pub fn push_numbered_move(&mut self, player: &Player, numbered_move: (u8, usize, usize)) {
+        let (number, row_index, col_index) = numbered_move;
+        self.moves.push(RecordedMove {
+            player: player.clone(),
+            layer_index: None,
+            row_index,
+            col_index,
+            symbol: None,
+            from: None,
+            pair: None,
+            collapse: None,
+            number: Some(number),
+            size: None,
+        });
+    }
+
+    // The collapse is chosen by the other player, but it belongs with the
+    // move whose mark closed the cycle.
+    pub fn record_collapse(&mut self, landed: (usize, usize)) {
+        if let Some(recorded) = self.moves.last_mut() {
+            recorded.collapse = Some(landed);
+        }
+    }
+
+    pub fn finish(&mut self, result: GameResult, termination: Termination) {
+        self.metadata.result = result;
+        self.metadata.termination = termination;
+    }
+}
+
+// Days-since-epoch to proleptic Gregorian date, per Howard Hinnant's
+// `civil_from_days`, so we don't need a whole date crate for one tag.
+pub fn current_date() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_piece = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_piece + 2) / 5 + 1;
+    let month = if month_piece < 10 {
+        month_piece + 3
+    } else {
+        month_piece - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
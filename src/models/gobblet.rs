@@ -0,0 +1,433 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::morris::REPETITION_LIMIT;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::winning_players_on;
+use crate::utils::gobblet_ai::line_score;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub const PIECES_PER_SIZE: usize = 2;
+
+type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PieceSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl PieceSize {
+    pub const ALL: [PieceSize; 3] = [PieceSize::Small, PieceSize::Medium, PieceSize::Large];
+
+    pub fn letter(&self) -> char {
+        match self {
+            PieceSize::Small => 'S',
+            PieceSize::Medium => 'M',
+            PieceSize::Large => 'L',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        PieceSize::ALL
+            .iter()
+            .find(|size| size.letter().eq_ignore_ascii_case(&letter))
+            .copied()
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PieceSize::Small => "small",
+            PieceSize::Medium => "medium",
+            PieceSize::Large => "large",
+        }
+    }
+}
+
+impl fmt::Display for PieceSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Piece {
+    pub player: Player,
+    pub size: PieceSize,
+}
+
+// The pieces on a cell, bottom first. Only the top one counts for lines.
+pub type Stack = Vec<Piece>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GobbletMove {
+    Place { size: PieceSize, to: Position },
+    Move { from: Position, to: Position },
+}
+
+impl GobbletMove {
+    pub fn destination(&self) -> Position {
+        match self {
+            GobbletMove::Place { to, .. } | GobbletMove::Move { to, .. } => *to,
+        }
+    }
+}
+
+// Gobblet Gobblers: two pieces of each size per player, and a bigger piece can
+// go over a smaller one of either colour, from the reserve or from elsewhere
+// on the board.
+#[derive(Debug, Clone)]
+pub struct Gobblet {
+    pub stacks: Vec<Vec<Stack>>,
+    reserves: [Vec<PieceSize>; 2],
+    pub first_player: Player,
+    pub moves_played: usize,
+    // How often each position has come up. The search's copies share it
+    // until they add a position of their own.
+    pub seen: Rc<HashMap<String, usize>>,
+}
+
+impl Gobblet {
+    pub fn new(first_player: &Player) -> Self {
+        let reserve: Vec<PieceSize> = PieceSize::ALL
+            .iter()
+            .flat_map(|&size| [size; PIECES_PER_SIZE])
+            .collect();
+
+        Self {
+            stacks: vec![vec![vec![]; 3]; 3],
+            reserves: [reserve.clone(), reserve],
+            first_player: first_player.clone(),
+            moves_played: 0,
+            seen: Rc::new(HashMap::new()),
+        }
+    }
+
+    pub fn reserve(&self, player: &Player) -> &[PieceSize] {
+        &self.reserves[player.index()]
+    }
+
+    pub fn top(&self, (row_index, col_index): Position) -> Option<&Piece> {
+        self.stacks[row_index][col_index].last()
+    }
+
+    // What can be seen from above: the top piece of each stack.
+    pub fn top_board(&self) -> Board {
+        let mut board = Board::new();
+        for (row_index, row) in self.stacks.iter().enumerate() {
+            for (col_index, stack) in row.iter().enumerate() {
+                if let Some(piece) = stack.last() {
+                    board.rows[row_index].cells[col_index] = Cell::Marker(piece.player.clone());
+                }
+            }
+        }
+        board
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.stacks.iter().enumerate().flat_map(|(row_index, row)| {
+            (0..row.len()).map(move |col_index| (row_index, col_index))
+        })
+    }
+
+    fn fits_over(&self, size: PieceSize, to: Position) -> bool {
+        self.top(to).is_none_or(|piece| piece.size < size)
+    }
+
+    pub fn moves_for(&self, player: &Player) -> Vec<GobbletMove> {
+        let mut sizes = self.reserve(player).to_vec();
+        sizes.dedup();
+
+        let places = sizes.into_iter().flat_map(|size| {
+            self.positions()
+                .filter(move |&to| self.fits_over(size, to))
+                .map(move |to| GobbletMove::Place { size, to })
+        });
+
+        let moves = self
+            .positions()
+            .filter_map(|from| match self.top(from) {
+                Some(piece) if &piece.player == player => Some((from, piece.size)),
+                _ => None,
+            })
+            .flat_map(|(from, size)| {
+                self.positions()
+                    .filter(move |&to| to != from && self.fits_over(size, to))
+                    .map(move |to| GobbletMove::Move { from, to })
+            });
+
+        places.chain(moves).collect()
+    }
+
+    // Lifting a piece can uncover a line for the other player. That line
+    // counts even if the move also makes one for the mover, so the mover
+    // loses.
+    pub fn winner(&self) -> Option<Player> {
+        let lines = winning_players_on(&self.top_board());
+        let last_mover = self.current_player().opponent();
+        lines
+            .iter()
+            .find(|player| **player != last_mover)
+            .or_else(|| lines.first())
+            .cloned()
+    }
+
+    pub fn repeated(&self) -> bool {
+        self.seen.values().any(|&count| count >= REPETITION_LIMIT)
+    }
+
+    pub fn validate_move(
+        &self,
+        player: &Player,
+        gobblet_move: GobbletMove,
+    ) -> Result<(), MoveError> {
+        let (to_row, to_col) = gobblet_move.destination();
+        let board_size = self.board_size();
+        if to_row >= board_size || to_col >= board_size {
+            return Err(MoveError::OutOfBounds {
+                row_index: to_row,
+                col_index: to_col,
+            });
+        }
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        let moving = match gobblet_move {
+            GobbletMove::Place { size, .. } => {
+                if !self.reserve(player).contains(&size) {
+                    return Err(MoveError::NoPieceLeft(size));
+                }
+                size
+            }
+            GobbletMove::Move { from, to } => {
+                if from.0 >= board_size || from.1 >= board_size {
+                    return Err(MoveError::OutOfBounds {
+                        row_index: from.0,
+                        col_index: from.1,
+                    });
+                }
+                if from == to {
+                    return Err(MoveError::StayedPut(from));
+                }
+                match self.top(from) {
+                    Some(piece) if &piece.player == player => piece.size,
+                    _ => return Err(MoveError::NotYourPiece(from)),
+                }
+            }
+        };
+
+        match self.top((to_row, to_col)) {
+            Some(piece) if piece.size >= moving => Err(MoveError::CannotCover {
+                row_index: to_row,
+                col_index: to_col,
+                size: moving,
+                covered: piece.size,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn board_size(&self) -> usize {
+        self.stacks.len()
+    }
+
+    // Every piece in every stack and whose turn it is. Each player owns a fixed
+    // set of pieces, so what's off the board follows from what's on it and
+    // the reserves don't need to be in the key.
+    pub fn position_key(&self, side_to_move: &Player) -> String {
+        let stacks: Vec<String> = self
+            .stacks
+            .iter()
+            .flatten()
+            .map(|stack| {
+                stack
+                    .iter()
+                    .map(|piece| format!("{}{}", piece.player.character(), piece.size.letter()))
+                    .collect()
+            })
+            .collect();
+
+        format!("{} {}", stacks.join("/"), side_to_move.character())
+    }
+}
+
+impl Rules for Gobblet {
+    type Move = GobbletMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Gobblet
+    }
+
+    fn current_player(&self) -> Player {
+        if self.moves_played.is_multiple_of(2) {
+            self.first_player.clone()
+        } else {
+            self.first_player.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<GobbletMove> {
+        self.moves_for(&self.current_player())
+    }
+
+    fn play(&mut self, player: &Player, gobblet_move: GobbletMove) -> Result<(), MoveError> {
+        self.validate_move(player, gobblet_move)?;
+        self.apply(player, gobblet_move);
+        Ok(())
+    }
+
+    // Counts the new position too, so the search sees repetitions along the
+    // line it's looking at.
+    fn apply(&mut self, player: &Player, gobblet_move: GobbletMove) {
+        let piece = match gobblet_move {
+            GobbletMove::Place { size, .. } => {
+                let reserve = &mut self.reserves[player.index()];
+                if let Some(index) = reserve.iter().position(|&left| left == size) {
+                    reserve.remove(index);
+                }
+                Piece {
+                    player: player.clone(),
+                    size,
+                }
+            }
+            GobbletMove::Move { from, .. } => match self.stacks[from.0][from.1].pop() {
+                Some(piece) => piece,
+                None => return,
+            },
+        };
+
+        let (to_row, to_col) = gobblet_move.destination();
+        self.stacks[to_row][to_col].push(piece);
+        self.moves_played += 1;
+
+        let key = self.position_key(&self.current_player());
+        *Rc::make_mut(&mut self.seen).entry(key).or_insert(0) += 1;
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::ThreeInARow));
+        }
+        if self.repeated() {
+            return Some(Outcome::draw(Termination::Repetition));
+        }
+        None
+    }
+
+    // Lines that can still be covered are only worth so much, so positions
+    // are judged by the visible tops.
+    fn evaluate(&self, player: &Player) -> i32 {
+        line_score(&self.top_board(), player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(size: PieceSize, to: Position) -> GobbletMove {
+        GobbletMove::Place { size, to }
+    }
+
+    fn piece(player: Player, size: PieceSize) -> Piece {
+        Piece { player, size }
+    }
+
+    #[test]
+    fn bigger_pieces_stack_on_smaller_ones() {
+        let mut gobblet = Gobblet::new(&Player::X);
+        gobblet
+            .play(&Player::X, place(PieceSize::Small, (1, 1)))
+            .unwrap();
+        gobblet
+            .play(&Player::O, place(PieceSize::Medium, (1, 1)))
+            .unwrap();
+
+        assert_eq!(gobblet.stacks[1][1].len(), 2);
+        assert_eq!(
+            gobblet.top((1, 1)),
+            Some(&piece(Player::O, PieceSize::Medium))
+        );
+    }
+
+    #[test]
+    fn only_smaller_pieces_can_be_covered() {
+        let mut gobblet = Gobblet::new(&Player::X);
+        gobblet
+            .play(&Player::X, place(PieceSize::Medium, (1, 1)))
+            .unwrap();
+
+        for size in [PieceSize::Small, PieceSize::Medium] {
+            assert_eq!(
+                gobblet.play(&Player::O, place(size, (1, 1))),
+                Err(MoveError::CannotCover {
+                    row_index: 1,
+                    col_index: 1,
+                    size,
+                    covered: PieceSize::Medium
+                })
+            );
+        }
+        assert_eq!(
+            gobblet.play(&Player::O, place(PieceSize::Large, (1, 1))),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn lifting_a_piece_can_give_the_other_player_a_line() {
+        let mut gobblet = Gobblet::new(&Player::X);
+        for col_index in 0..3 {
+            gobblet.stacks[0][col_index].push(piece(Player::O, PieceSize::Small));
+        }
+        gobblet.stacks[0][2].push(piece(Player::X, PieceSize::Large));
+        assert_eq!(gobblet.winner(), None);
+
+        let lift = GobbletMove::Move {
+            from: (0, 2),
+            to: (1, 1),
+        };
+        gobblet.play(&Player::X, lift).unwrap();
+        assert_eq!(gobblet.winner(), Some(Player::O));
+    }
+
+    #[test]
+    fn a_size_runs_out_once_both_pieces_are_down() {
+        let mut gobblet = Gobblet::new(&Player::X);
+        for (player, to) in [
+            (Player::X, (0, 0)),
+            (Player::O, (2, 2)),
+            (Player::X, (0, 1)),
+            (Player::O, (2, 1)),
+        ] {
+            gobblet.play(&player, place(PieceSize::Small, to)).unwrap();
+        }
+
+        assert!(!gobblet.reserve(&Player::X).contains(&PieceSize::Small));
+        assert!(!gobblet
+            .moves_for(&Player::X)
+            .contains(&place(PieceSize::Small, (1, 1))));
+        assert_eq!(
+            gobblet.play(&Player::X, place(PieceSize::Small, (1, 1))),
+            Err(MoveError::NoPieceLeft(PieceSize::Small))
+        );
+    }
+}
@@ -0,0 +1,506 @@
+use crate::error::MoveError;
+use crate::models::board::{Board, BoardSize};
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::board_full;
+use crate::utils::validation::check_empty;
+use serde::{Deserialize, Serialize};
+
+pub type Position = (usize, usize);
+
+// Black is X and always moves first; White is O.
+pub const BLACK: Player = Player::X;
+pub const WHITE: Player = Player::O;
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GomokuRule {
+    // Five or more in a row wins.
+    Freestyle,
+    // Exactly five wins; six or more counts for nothing.
+    #[default]
+    Standard,
+    // Black needs exactly five and may not play an overline, a double four
+    // or a double three; White wins with five or more.
+    Renju,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Opening {
+    #[default]
+    Plain,
+    // The first player puts down two black stones and a white one, then the
+    // other picks a colour.
+    Swap,
+    // As swap, but the other player may instead add a white and a black
+    // stone and hand the choice back.
+    Swap2,
+}
+
+// What the player offered the swap does with it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SwapChoice {
+    Take(Player),
+    // Swap2 only: add a white and a black stone and let the other choose.
+    AddTwo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Forbidden {
+    Overline,
+    DoubleFour,
+    DoubleThree,
+}
+
+impl GomokuRule {
+    pub const ALL: [GomokuRule; 3] = [
+        GomokuRule::Freestyle,
+        GomokuRule::Standard,
+        GomokuRule::Renju,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            GomokuRule::Freestyle => "freestyle",
+            GomokuRule::Standard => "standard",
+            GomokuRule::Renju => "renju",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        GomokuRule::ALL
+            .iter()
+            .find(|rule| rule.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            GomokuRule::Freestyle => "five or more in a row wins",
+            GomokuRule::Standard => "exactly five in a row wins",
+            GomokuRule::Renju => "Renju: Black needs exactly five and can't play forbidden moves",
+        }
+    }
+
+    // Whether a run longer than five still wins for this player.
+    fn overline_wins(&self, player: &Player) -> bool {
+        match self {
+            GomokuRule::Freestyle => true,
+            GomokuRule::Standard => false,
+            GomokuRule::Renju => player == &WHITE,
+        }
+    }
+}
+
+impl Opening {
+    pub const ALL: [Opening; 3] = [Opening::Plain, Opening::Swap, Opening::Swap2];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Opening::Plain => "plain",
+            Opening::Swap => "swap",
+            Opening::Swap2 => "swap2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Opening::ALL
+            .iter()
+            .find(|opening| opening.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+}
+
+impl Forbidden {
+    pub fn description(&self) -> &str {
+        match self {
+            Forbidden::Overline => "it makes six or more in a row",
+            Forbidden::DoubleFour => "it makes two fours at once",
+            Forbidden::DoubleThree => "it makes two open threes at once",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gomoku {
+    pub board: Board,
+    pub rule: GomokuRule,
+    pub winner: Option<Player>,
+}
+
+impl Gomoku {
+    pub fn new(size: BoardSize, rule: GomokuRule) -> Self {
+        Self {
+            board: Board::with_size(size),
+            rule,
+            winner: None,
+        }
+    }
+
+    pub fn stone_count(&self, player: &Player) -> usize {
+        let stone = Cell::Marker(player.clone());
+        self.board
+            .get_cells()
+            .iter()
+            .filter(|cell| ***cell == stone)
+            .count()
+    }
+
+    pub fn open_positions(&self) -> Vec<Position> {
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect()
+    }
+
+    // Whether a stone at an empty cell would win for the player.
+    pub fn wins_at(&self, player: &Player, position: Position) -> bool {
+        let mut board = self.board.clone();
+        place(&mut board, position, player);
+        makes_five(&board, position, player, self.rule.overline_wins(player))
+    }
+
+    // Why Black may not play at an empty cell under Renju, if they may not.
+    // A move that makes exactly five is always allowed.
+    pub fn forbidden(&self, player: &Player, position: Position) -> Option<Forbidden> {
+        if self.rule != GomokuRule::Renju || player != &BLACK {
+            return None;
+        }
+
+        let mut board = self.board.clone();
+        place(&mut board, position, player);
+        let five = self.board.win_length;
+        if makes_five(&board, position, player, false) {
+            return None;
+        }
+
+        let runs: Vec<usize> = DIRECTIONS
+            .iter()
+            .map(|&direction| run_through(&board, position, direction, player).len())
+            .collect();
+        if runs.iter().any(|&run| run > five) {
+            return Some(Forbidden::Overline);
+        }
+
+        let fours: usize = DIRECTIONS
+            .iter()
+            .map(|&direction| fours_in(&mut board, position, direction, five))
+            .sum();
+        if fours >= 2 {
+            return Some(Forbidden::DoubleFour);
+        }
+
+        let threes = DIRECTIONS
+            .iter()
+            .filter(|&&direction| is_open_three(&mut board, position, direction, five))
+            .count();
+        if threes >= 2 {
+            return Some(Forbidden::DoubleThree);
+        }
+
+        None
+    }
+}
+
+impl Rules for Gomoku {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        Variant::Gomoku
+    }
+
+    // Colours alternate whoever sits behind them, so the counts say whose
+    // stone comes next even through the opening.
+    fn current_player(&self) -> Player {
+        if self.stone_count(&BLACK) > self.stone_count(&WHITE) {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<Position> {
+        let player = self.current_player();
+        self.open_positions()
+            .into_iter()
+            .filter(|&position| self.forbidden(&player, position).is_none())
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, position: Position) -> Result<(), MoveError> {
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if player != &expected {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        let (row_index, col_index) = position;
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+        check_empty(cell, row_index, col_index)?;
+
+        if let Some(reason) = self.forbidden(player, position) {
+            return Err(MoveError::Forbidden {
+                row_index,
+                col_index,
+                reason,
+            });
+        }
+
+        self.apply(player, position);
+        Ok(())
+    }
+
+    fn apply(&mut self, player: &Player, position: Position) {
+        if self.wins_at(player, position) {
+            self.winner = Some(player.clone());
+        }
+        place(&mut self.board, position, player);
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner.clone() {
+            return Some(Outcome::win(winner, Termination::LineCompleted));
+        }
+        if board_full(&self.board) {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+        None
+    }
+}
+
+fn place(board: &mut Board, (row_index, col_index): Position, player: &Player) {
+    board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+}
+
+fn clear(board: &mut Board, (row_index, col_index): Position) {
+    board.rows[row_index].cells[col_index] = Cell::Empty;
+}
+
+fn step(
+    board: &Board,
+    (row_index, col_index): Position,
+    (dr, dc): (isize, isize),
+    distance: isize,
+) -> Option<Position> {
+    let size = board.size();
+    let row_index = row_index as isize + dr * distance;
+    let col_index = col_index as isize + dc * distance;
+    if (0..size.rows as isize).contains(&row_index) && (0..size.cols as isize).contains(&col_index)
+    {
+        Some((row_index as usize, col_index as usize))
+    } else {
+        None
+    }
+}
+
+fn cell(board: &Board, position: Option<Position>) -> Option<&Cell> {
+    position.map(|(row_index, col_index)| &board.rows[row_index].cells[col_index])
+}
+
+// The unbroken run of the player's stones along a direction through a cell
+// that holds one of them.
+fn run_through(
+    board: &Board,
+    position: Position,
+    direction: (isize, isize),
+    player: &Player,
+) -> Vec<Position> {
+    let stone = Cell::Marker(player.clone());
+    let reach = |sign: isize| {
+        (1..)
+            .map(|distance| step(board, position, direction, sign * distance))
+            .take_while(|next| cell(board, *next) == Some(&stone))
+            .flatten()
+            .collect::<Vec<Position>>()
+    };
+
+    let mut run: Vec<Position> = reach(-1).into_iter().rev().collect();
+    run.push(position);
+    run.extend(reach(1));
+    run
+}
+
+fn makes_five(board: &Board, position: Position, player: &Player, overline_wins: bool) -> bool {
+    let five = board.win_length;
+    DIRECTIONS.iter().any(|&direction| {
+        let run = run_through(board, position, direction, player).len();
+        run == five || (overline_wins && run > five)
+    })
+}
+
+// Counts Black's fours along one direction through a new stone: the ways one
+// more stone makes exactly five. An open four can be finished at either end
+// but it is one four; a broken line like XXX.X.XXX holds two.
+fn fours_in(
+    board: &mut Board,
+    position: Position,
+    direction: (isize, isize),
+    five: usize,
+) -> usize {
+    let mut fours: Vec<Vec<Position>> = vec![];
+    for distance in 1..five as isize {
+        for sign in [-1, 1] {
+            let Some(gap) = step(board, position, direction, sign * distance) else {
+                continue;
+            };
+            if cell(board, Some(gap)) != Some(&Cell::Empty) {
+                continue;
+            }
+
+            place(board, gap, &BLACK);
+            let run = run_through(board, gap, direction, &BLACK);
+            clear(board, gap);
+
+            if run.len() == five && run.contains(&position) {
+                let four: Vec<Position> = run.into_iter().filter(|&cell| cell != gap).collect();
+                if !fours.contains(&four) {
+                    fours.push(four);
+                }
+            }
+        }
+    }
+    fours.len()
+}
+
+// Whether one more black stone along the direction turns the new stone's
+// line into an open four, one that could be finished into exactly five at
+// either end.
+fn is_open_three(
+    board: &mut Board,
+    position: Position,
+    direction: (isize, isize),
+    five: usize,
+) -> bool {
+    (1..five as isize).any(|distance| {
+        [-1, 1].iter().any(|&sign| {
+            let Some(gap) = step(board, position, direction, sign * distance) else {
+                return false;
+            };
+            if cell(board, Some(gap)) != Some(&Cell::Empty) {
+                return false;
+            }
+
+            place(board, gap, &BLACK);
+            let run = run_through(board, gap, direction, &BLACK);
+            let open = run.len() == five - 1
+                && run.contains(&position)
+                && is_open_end(board, run[0], direction, -1)
+                && is_open_end(board, run[run.len() - 1], direction, 1);
+            clear(board, gap);
+            open
+        })
+    })
+}
+
+// The cell past the end of a run is empty and filling it wouldn't run into
+// another black stone beyond, which would make an overline instead.
+fn is_open_end(board: &Board, end: Position, direction: (isize, isize), sign: isize) -> bool {
+    let black = Cell::Marker(BLACK);
+    cell(board, step(board, end, direction, sign)) == Some(&Cell::Empty)
+        && cell(board, step(board, end, direction, 2 * sign)) != Some(&black)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renju(black: &[Position], white: &[Position]) -> Gomoku {
+        let mut gomoku = Gomoku::new(BoardSize::GOMOKU, GomokuRule::Renju);
+        for &position in black {
+            place(&mut gomoku.board, position, &BLACK);
+        }
+        for &position in white {
+            place(&mut gomoku.board, position, &WHITE);
+        }
+        gomoku
+    }
+
+    #[test]
+    fn two_open_threes_are_forbidden() {
+        let gomoku = renju(&[(7, 5), (7, 6), (5, 7), (6, 7)], &[]);
+        assert_eq!(
+            gomoku.forbidden(&BLACK, (7, 7)),
+            Some(Forbidden::DoubleThree)
+        );
+        assert_eq!(gomoku.forbidden(&WHITE, (7, 7)), None);
+    }
+
+    #[test]
+    fn split_threes_count_as_threes() {
+        // X.XX across and down.
+        let gomoku = renju(&[(7, 4), (7, 6), (4, 7), (6, 7)], &[]);
+        assert_eq!(
+            gomoku.forbidden(&BLACK, (7, 7)),
+            Some(Forbidden::DoubleThree)
+        );
+    }
+
+    #[test]
+    fn a_three_blocked_at_one_end_is_not_open() {
+        let gomoku = renju(&[(7, 5), (7, 6), (5, 7), (6, 7)], &[(7, 4)]);
+        assert_eq!(gomoku.forbidden(&BLACK, (7, 7)), None);
+    }
+
+    #[test]
+    fn the_board_edge_closes_off_a_three() {
+        let gomoku = renju(&[(7, 0), (7, 1), (5, 2), (6, 2)], &[]);
+        assert_eq!(gomoku.forbidden(&BLACK, (7, 2)), None);
+
+        // Two cells further in, the same shape is a double three.
+        let gomoku = renju(&[(7, 2), (7, 3), (5, 4), (6, 4)], &[]);
+        assert_eq!(
+            gomoku.forbidden(&BLACK, (7, 4)),
+            Some(Forbidden::DoubleThree)
+        );
+    }
+
+    #[test]
+    fn two_fours_are_forbidden() {
+        let gomoku = renju(&[(7, 4), (7, 5), (7, 6), (4, 7), (5, 7), (6, 7)], &[]);
+        assert_eq!(
+            gomoku.forbidden(&BLACK, (7, 7)),
+            Some(Forbidden::DoubleFour)
+        );
+    }
+
+    #[test]
+    fn six_in_a_row_is_forbidden() {
+        let gomoku = renju(&[(7, 2), (7, 3), (7, 4), (7, 6), (7, 7)], &[]);
+        assert_eq!(gomoku.forbidden(&BLACK, (7, 5)), Some(Forbidden::Overline));
+        assert!(!gomoku.wins_at(&BLACK, (7, 5)));
+    }
+
+    #[test]
+    fn a_five_is_allowed_even_if_it_also_makes_six() {
+        let gomoku = renju(
+            &[
+                (7, 3),
+                (7, 4),
+                (7, 6),
+                (7, 7),
+                (4, 5),
+                (5, 5),
+                (6, 5),
+                (8, 5),
+                (9, 5),
+            ],
+            &[],
+        );
+        assert_eq!(gomoku.forbidden(&BLACK, (7, 5)), None);
+        assert!(gomoku.wins_at(&BLACK, (7, 5)));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::error::MoveError;
+use crate::models::board::{Board, BoardSize};
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, current_player};
+use crate::utils::gravity_ai::{drop_columns, evaluate};
+use crate::utils::lines::Lines;
+use crate::utils::validation::play_move;
+use std::rc::Rc;
+
+pub type Position = (usize, usize);
+
+// Marks fall to the lowest empty cell of the column they're dropped in.
+#[derive(Debug, Clone)]
+pub struct Gravity {
+    pub board: Board,
+    pub first_player: Player,
+    pub last_drop: Option<Position>,
+    // Shared between the copies the search makes, since the board's shape
+    // never changes.
+    pub lines: Rc<Lines>,
+}
+
+impl Gravity {
+    pub fn new(size: BoardSize, first_player: &Player) -> Self {
+        let board = Board::with_size(size);
+        Self {
+            lines: Rc::new(Lines::of(&board)),
+            board,
+            first_player: first_player.clone(),
+            last_drop: None,
+        }
+    }
+
+    pub fn landing_cell(&self, col_index: usize) -> Option<Position> {
+        self.board
+            .lowest_empty_row(col_index)
+            .map(|row_index| (row_index, col_index))
+    }
+}
+
+impl Rules for Gravity {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        Variant::Gravity
+    }
+
+    fn current_player(&self) -> Player {
+        current_player(&self.board, &self.first_player)
+    }
+
+    fn legal_moves(&self) -> Vec<Position> {
+        (0..self.board.size().cols)
+            .filter_map(|col_index| self.landing_cell(col_index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, (row_index, col_index): Position) -> Result<(), MoveError> {
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+        if *cell == Cell::Empty && self.landing_cell(col_index) != Some((row_index, col_index)) {
+            return Err(MoveError::Floating((row_index, col_index)));
+        }
+
+        play_move(
+            &mut self.board,
+            &self.first_player,
+            player,
+            row_index,
+            col_index,
+        )?;
+        self.last_drop = Some((row_index, col_index));
+        Ok(())
+    }
+
+    fn apply(&mut self, player: &Player, (row_index, col_index): Position) {
+        self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+        self.last_drop = Some((row_index, col_index));
+    }
+
+    // Only the last mark dropped can have finished a line.
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some((row_index, col_index)) = self.last_drop {
+            if let Cell::Marker(player) = &self.board.rows[row_index].cells[col_index] {
+                if self
+                    .lines
+                    .completed_by(&self.board, (row_index, col_index), player)
+                {
+                    return Some(Outcome::win(player.clone(), Termination::LineCompleted));
+                }
+            }
+        }
+
+        if board_full(&self.board) {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+
+        None
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        evaluate(&self.board, &self.lines, player)
+    }
+
+    fn candidate_moves(&self) -> Vec<Position> {
+        drop_columns(&self.board)
+            .into_iter()
+            .filter_map(|col_index| self.landing_cell(col_index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_land_on_the_lowest_empty_row() {
+        let mut gravity = Gravity::new(BoardSize::CONNECT_FOUR, &Player::X);
+        assert_eq!(gravity.landing_cell(2), Some((5, 2)));
+
+        gravity.play(&Player::X, (5, 2)).unwrap();
+        assert_eq!(gravity.landing_cell(2), Some((4, 2)));
+        assert_eq!(gravity.last_drop, Some((5, 2)));
+    }
+
+    #[test]
+    fn a_mark_with_nothing_under_it_is_rejected() {
+        let mut gravity = Gravity::new(BoardSize::CONNECT_FOUR, &Player::X);
+        assert_eq!(
+            gravity.play(&Player::X, (0, 3)),
+            Err(MoveError::Floating((0, 3)))
+        );
+    }
+
+    #[test]
+    fn a_full_column_takes_no_more_marks() {
+        let mut gravity = Gravity::new(BoardSize::CONNECT_FOUR, &Player::X);
+        for row_index in (0..6).rev() {
+            let player = gravity.current_player();
+            gravity.play(&player, (row_index, 0)).unwrap();
+        }
+
+        assert_eq!(gravity.landing_cell(0), None);
+        assert!(!gravity.legal_moves().contains(&(0, 0)));
+        assert_eq!(
+            gravity.play(&Player::X, (0, 0)),
+            Err(MoveError::Occupied {
+                row_index: 0,
+                col_index: 0,
+                by: Player::O
+            })
+        );
+    }
+}
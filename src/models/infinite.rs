@@ -0,0 +1,269 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::winning_player_on;
+use crate::utils::infinite_ai::evaluate;
+use crate::utils::notation::{cell_name, position_string};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+pub const MARKS_KEPT: usize = 3;
+pub const MOVE_LIMIT: usize = 100;
+pub const REPETITION_LIMIT: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Infinite {
+    pub board: Board,
+    pub x_marks: VecDeque<(usize, usize)>,
+    pub o_marks: VecDeque<(usize, usize)>,
+    pub first_player: Player,
+    pub moves_played: usize,
+    // How often each position has come up. The search's copies share it
+    // until they add a position of their own.
+    pub seen: Rc<HashMap<String, usize>>,
+}
+
+impl Infinite {
+    pub fn new(first_player: &Player) -> Self {
+        Self {
+            board: Board::new(),
+            x_marks: VecDeque::new(),
+            o_marks: VecDeque::new(),
+            first_player: first_player.clone(),
+            moves_played: 0,
+            seen: Rc::new(HashMap::new()),
+        }
+    }
+
+    // Each player's marks, oldest first. Only X and O ever sit down to this.
+    pub fn marks_of(&self, player: &Player) -> &VecDeque<(usize, usize)> {
+        match player {
+            Player::X => &self.x_marks,
+            _ => &self.o_marks,
+        }
+    }
+
+    fn marks_of_mut(&mut self, player: &Player) -> &mut VecDeque<(usize, usize)> {
+        match player {
+            Player::X => &mut self.x_marks,
+            _ => &mut self.o_marks,
+        }
+    }
+
+    // The mark this player loses on their next move, if they're at the limit.
+    pub fn fading_mark(&self, player: &Player) -> Option<(usize, usize)> {
+        let marks = self.marks_of(player);
+        if marks.len() == MARKS_KEPT {
+            marks.front().copied()
+        } else {
+            None
+        }
+    }
+
+    // The board alone isn't the whole state: which mark goes next matters too.
+    pub fn position_key(&self, side_to_move: &Player) -> String {
+        let order = |marks: &VecDeque<(usize, usize)>| {
+            marks
+                .iter()
+                .map(|&(row_index, col_index)| cell_name(row_index, col_index))
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        format!(
+            "{} {} {}",
+            position_string(&self.board, side_to_move),
+            order(&self.x_marks),
+            order(&self.o_marks)
+        )
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        winning_player_on(&self.board)
+    }
+
+    pub fn repeated(&self) -> bool {
+        self.seen.values().any(|&count| count >= REPETITION_LIMIT)
+    }
+
+    pub fn out_of_moves(&self) -> bool {
+        self.moves_played >= MOVE_LIMIT
+    }
+}
+
+impl Rules for Infinite {
+    type Move = (usize, usize);
+
+    fn variant(&self) -> Variant {
+        Variant::Infinite
+    }
+
+    fn current_player(&self) -> Player {
+        if self.moves_played.is_multiple_of(2) {
+            self.first_player.clone()
+        } else {
+            self.first_player.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<(usize, usize)> {
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, position: (usize, usize)) -> Result<(), MoveError> {
+        let (row_index, col_index) = position;
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        if let Cell::Marker(by) = cell {
+            return Err(MoveError::Occupied {
+                row_index,
+                col_index,
+                by: by.clone(),
+            });
+        }
+
+        self.apply(player, position);
+        Ok(())
+    }
+
+    // Drops the player's oldest mark first if they're at the limit, and
+    // counts the new position so the search sees repetitions along the line
+    // it's looking at.
+    fn apply(&mut self, player: &Player, position: (usize, usize)) {
+        if let Some((row_index, col_index)) = self.fading_mark(player) {
+            self.board.rows[row_index].cells[col_index] = Cell::Empty;
+            self.marks_of_mut(player).pop_front();
+        }
+
+        let (row_index, col_index) = position;
+        self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+        self.marks_of_mut(player).push_back(position);
+        self.moves_played += 1;
+
+        let key = self.position_key(&self.current_player());
+        *Rc::make_mut(&mut self.seen).entry(key).or_insert(0) += 1;
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::ThreeInARow));
+        }
+        if self.repeated() {
+            return Some(Outcome::draw(Termination::Repetition));
+        }
+        if self.out_of_moves() {
+            return Some(Outcome::draw(Termination::MoveLimit));
+        }
+        None
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        evaluate(self, player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(positions: &[(usize, usize)]) -> Infinite {
+        let mut infinite = Infinite::new(&Player::X);
+        for &position in positions {
+            let player = infinite.current_player();
+            infinite.play(&player, position).unwrap();
+        }
+        infinite
+    }
+
+    #[test]
+    fn a_fourth_mark_takes_the_oldest_off() {
+        let mut infinite = played(&[(0, 0), (1, 0), (0, 2), (1, 2), (2, 1), (2, 0)]);
+        assert_eq!(infinite.fading_mark(&Player::X), Some((0, 0)));
+
+        infinite.play(&Player::X, (1, 1)).unwrap();
+        assert_eq!(infinite.board.rows[0].cells[0], Cell::Empty);
+        assert_eq!(
+            infinite.marks_of(&Player::X),
+            &VecDeque::from([(0, 2), (2, 1), (1, 1)])
+        );
+    }
+
+    #[test]
+    fn the_third_time_a_position_comes_up_is_a_draw() {
+        // Each player cycles round four cells, none three of which make a
+        // line, so every eight moves the position comes back.
+        let opening = [(0, 0), (1, 0), (0, 1), (1, 1), (1, 2), (2, 0)];
+        let cycle = [
+            (2, 2),
+            (2, 1),
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (2, 0),
+        ];
+        let mut infinite = played(&opening);
+
+        for &position in cycle.iter().cycle().take(15) {
+            let player = infinite.current_player();
+            infinite.play(&player, position).unwrap();
+            assert_eq!(infinite.outcome(), None);
+        }
+
+        infinite.play(&Player::O, (2, 0)).unwrap();
+        assert_eq!(
+            infinite.outcome(),
+            Some(Outcome::draw(Termination::Repetition))
+        );
+    }
+
+    #[test]
+    fn the_game_is_drawn_at_the_move_limit() {
+        let mut infinite = Infinite::new(&Player::X);
+        infinite.moves_played = MOVE_LIMIT - 2;
+
+        infinite.play(&Player::X, (0, 0)).unwrap();
+        assert_eq!(infinite.outcome(), None);
+        infinite.play(&Player::O, (1, 1)).unwrap();
+        assert_eq!(
+            infinite.outcome(),
+            Some(Outcome::draw(Termination::MoveLimit))
+        );
+    }
+
+    #[test]
+    fn the_position_key_tells_apart_marks_placed_in_another_order() {
+        let first = played(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let second = played(&[(0, 1), (1, 0), (0, 0), (1, 1)]);
+
+        assert_eq!(
+            position_string(&first.board, &Player::X),
+            position_string(&second.board, &Player::X)
+        );
+        assert_ne!(
+            first.position_key(&Player::X),
+            second.position_key(&Player::X)
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, winning_player_on};
+use crate::utils::ultimate_ai::evaluate;
+use crate::utils::validation::check_empty;
+
+pub type BoardPosition = (usize, usize);
+pub type MetaMove = (BoardPosition, (usize, usize));
+
+#[derive(Debug, Clone)]
+pub struct MetaBoard {
+    pub boards: [[Board; 3]; 3],
+    pub next_board: Option<BoardPosition>,
+    pub first_player: Player,
+}
+
+impl MetaBoard {
+    pub fn new(first_player: &Player) -> Self {
+        Self {
+            boards: [
+                [Board::new(), Board::new(), Board::new()],
+                [Board::new(), Board::new(), Board::new()],
+                [Board::new(), Board::new(), Board::new()],
+            ],
+            next_board: None,
+            first_player: first_player.clone(),
+        }
+    }
+
+    pub fn get_board_at(&self, board_position: BoardPosition) -> Result<&Board, MoveError> {
+        let (board_row, board_col) = board_position;
+        self.boards
+            .get(board_row)
+            .and_then(|row| row.get(board_col))
+            .ok_or(MoveError::OutOfBounds {
+                row_index: board_row,
+                col_index: board_col,
+            })
+    }
+
+    // A board where each cell holds whoever won the matching sub-board, so the
+    // regular win detection works on the meta level too.
+    pub fn claims(&self) -> Board {
+        let mut claims = Board::new();
+        for (board_row, row) in self.boards.iter().enumerate() {
+            for (board_col, board) in row.iter().enumerate() {
+                if let Some(winner) = winning_player_on(board) {
+                    claims.rows[board_row].cells[board_col] = Cell::Marker(winner);
+                }
+            }
+        }
+        claims
+    }
+
+    pub fn board_decided(&self, board_position: BoardPosition) -> bool {
+        self.get_board_at(board_position).map_or(true, |board| {
+            board_full(board) || winning_player_on(board).is_some()
+        })
+    }
+
+    pub fn available_boards(&self) -> Vec<BoardPosition> {
+        if self.winner().is_some() {
+            return vec![];
+        }
+
+        match self.next_board {
+            Some(board_position) if !self.board_decided(board_position) => vec![board_position],
+            _ => (0..3)
+                .flat_map(|board_row| (0..3).map(move |board_col| (board_row, board_col)))
+                .filter(|&board_position| !self.board_decided(board_position))
+                .collect(),
+        }
+    }
+
+    pub fn all_cells(&self) -> Vec<&Cell> {
+        self.boards
+            .iter()
+            .flat_map(|row| row.iter().flat_map(|board| board.get_cells()))
+            .collect()
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        winning_player_on(&self.claims())
+    }
+
+    pub fn validate_move(&self, player: &Player, meta_move: MetaMove) -> Result<(), MoveError> {
+        let (board_position, (row_index, col_index)) = meta_move;
+        let cell = self
+            .get_board_at(board_position)?
+            .get_cell_at(row_index, col_index)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        let available_boards = self.available_boards();
+        if !available_boards.contains(&board_position) {
+            return Err(match self.next_board {
+                Some(forced) if available_boards == vec![forced] => MoveError::WrongBoard {
+                    expected: forced,
+                    attempted: board_position,
+                },
+                _ => MoveError::BoardDecided(board_position),
+            });
+        }
+
+        check_empty(cell, row_index, col_index)
+    }
+}
+
+impl Rules for MetaBoard {
+    type Move = MetaMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Ultimate
+    }
+
+    fn current_player(&self) -> Player {
+        let cells = self.all_cells();
+        let count = |player: &Player| {
+            cells
+                .iter()
+                .filter(|cell| ***cell == Cell::Marker(player.clone()))
+                .count()
+        };
+
+        if count(&self.first_player) > count(&self.first_player.opponent()) {
+            self.first_player.opponent()
+        } else {
+            self.first_player.clone()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<MetaMove> {
+        self.available_boards()
+            .into_iter()
+            .flat_map(|board_position| {
+                let (board_row, board_col) = board_position;
+                self.boards[board_row][board_col]
+                    .get_cell_positions()
+                    .into_iter()
+                    .filter(|(cell, _, _)| **cell == Cell::Empty)
+                    .map(move |(_, row_index, col_index)| (board_position, (row_index, col_index)))
+                    .collect::<Vec<MetaMove>>()
+            })
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, meta_move: MetaMove) -> Result<(), MoveError> {
+        self.validate_move(player, meta_move)?;
+        self.apply(player, meta_move);
+        Ok(())
+    }
+
+    fn apply(&mut self, player: &Player, meta_move: MetaMove) {
+        let ((board_row, board_col), (row_index, col_index)) = meta_move;
+        self.boards[board_row][board_col].rows[row_index].cells[col_index] =
+            Cell::Marker(player.clone());
+        self.next_board = Some((row_index, col_index));
+    }
+
+    // Over once the claims make a line or no board is left open.
+    fn outcome(&self) -> Option<Outcome> {
+        match self.winner() {
+            Some(winner) => Some(Outcome::win(winner, Termination::ThreeInARow)),
+            None if self.available_boards().is_empty() => {
+                Some(Outcome::draw(Termination::BoardFull))
+            }
+            None => None,
+        }
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        evaluate(self, player)
+    }
+}
+
+pub fn to_global(meta_move: MetaMove) -> (usize, usize) {
+    let ((board_row, board_col), (row_index, col_index)) = meta_move;
+    (board_row * 3 + row_index, board_col * 3 + col_index)
+}
+
+pub fn from_global(row_index: usize, col_index: usize) -> MetaMove {
+    (
+        (row_index / 3, col_index / 3),
+        (row_index % 3, col_index % 3),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::notation::read_position;
+
+    const WON_BY_X: &str = "XXX/OO_/___ o";
+    const DRAWN: &str = "OXO/OXX/XOO x";
+
+    fn set_board(
+        meta_board: &mut MetaBoard,
+        (board_row, board_col): BoardPosition,
+        position: &str,
+    ) {
+        meta_board.boards[board_row][board_col] = read_position(position).unwrap().0;
+    }
+
+    #[test]
+    fn a_move_sends_the_next_player_to_the_matching_board() {
+        let mut meta_board = MetaBoard::new(&Player::X);
+        meta_board.play(&Player::X, ((0, 0), (1, 2))).unwrap();
+
+        assert_eq!(meta_board.available_boards(), vec![(1, 2)]);
+        assert_eq!(
+            meta_board.play(&Player::O, ((0, 0), (0, 0))),
+            Err(MoveError::WrongBoard {
+                expected: (1, 2),
+                attempted: (0, 0)
+            })
+        );
+        assert_eq!(meta_board.play(&Player::O, ((1, 2), (0, 0))), Ok(()));
+        assert_eq!(meta_board.next_board, Some((0, 0)));
+    }
+
+    #[test]
+    fn being_sent_to_a_won_or_full_board_frees_the_move() {
+        let mut meta_board = MetaBoard::new(&Player::X);
+        set_board(&mut meta_board, (1, 1), WON_BY_X);
+        set_board(&mut meta_board, (2, 2), DRAWN);
+
+        meta_board.play(&Player::X, ((0, 0), (1, 1))).unwrap();
+        assert_eq!(meta_board.available_boards().len(), 7);
+        assert_eq!(
+            meta_board.play(&Player::O, ((1, 1), (2, 2))),
+            Err(MoveError::BoardDecided((1, 1)))
+        );
+
+        meta_board.play(&Player::O, ((0, 1), (2, 2))).unwrap();
+        assert_eq!(meta_board.available_boards().len(), 7);
+        assert_eq!(meta_board.play(&Player::X, ((2, 0), (0, 0))), Ok(()));
+    }
+
+    #[test]
+    fn won_boards_are_claimed_on_the_meta_board() {
+        let mut meta_board = MetaBoard::new(&Player::X);
+        set_board(&mut meta_board, (0, 0), WON_BY_X);
+        set_board(&mut meta_board, (0, 1), WON_BY_X);
+        set_board(&mut meta_board, (2, 2), DRAWN);
+
+        let claims = meta_board.claims();
+        assert_eq!(claims.rows[0].cells[0], Cell::Marker(Player::X));
+        assert_eq!(claims.rows[0].cells[1], Cell::Marker(Player::X));
+        assert_eq!(claims.rows[2].cells[2], Cell::Empty);
+        assert_eq!(meta_board.winner(), None);
+
+        set_board(&mut meta_board, (0, 2), WON_BY_X);
+        assert_eq!(meta_board.winner(), Some(Player::X));
+        assert_eq!(
+            meta_board.outcome(),
+            Some(Outcome::win(Player::X, Termination::ThreeInARow))
+        );
+    }
+}
@@ -1,4 +1,26 @@
 pub mod board;
 pub mod cell;
+pub mod classic;
+pub mod cube;
+pub mod custom;
+pub mod definition;
+pub mod game_record;
+pub mod gobblet;
+pub mod gomoku;
+pub mod gravity;
+pub mod infinite;
+pub mod meta_board;
+pub mod morris;
+pub mod multiplayer;
+pub mod notakto;
+pub mod numerical;
+pub mod order_chaos;
+pub mod phantom;
 pub mod player;
+pub mod quantum;
 pub mod row;
+pub mod rules;
+pub mod symbol;
+pub mod turn_schedule;
+pub mod variant;
+pub mod wild;
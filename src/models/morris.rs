@@ -0,0 +1,271 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::winning_player_on;
+use crate::utils::notation::position_string;
+use crate::utils::validation::check_empty;
+use crate::utils::validation::count_markers;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub const PIECES_PER_PLAYER: usize = 3;
+pub const REPETITION_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MorrisMove {
+    Place((usize, usize)),
+    Slide {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Morris {
+    pub board: Board,
+    pub first_player: Player,
+    pub moves_played: usize,
+    // How often each position has come up. The search's copies share it
+    // until they add a position of their own.
+    pub seen: Rc<HashMap<String, usize>>,
+}
+
+impl MorrisMove {
+    pub fn destination(&self) -> (usize, usize) {
+        match self {
+            MorrisMove::Place(position) => *position,
+            MorrisMove::Slide { to, .. } => *to,
+        }
+    }
+}
+
+impl Morris {
+    pub fn new(first_player: &Player) -> Self {
+        Self {
+            board: Board::new(),
+            first_player: first_player.clone(),
+            moves_played: 0,
+            seen: Rc::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_placing(&self, player: &Player) -> bool {
+        count_markers(&self.board, player) < PIECES_PER_PLAYER
+    }
+
+    pub fn moves_for(&self, player: &Player) -> Vec<MorrisMove> {
+        let empty_positions = self
+            .board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect::<Vec<(usize, usize)>>();
+
+        if self.is_placing(player) {
+            return empty_positions.into_iter().map(MorrisMove::Place).collect();
+        }
+
+        let own_cell = Cell::Marker(player.clone());
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == own_cell)
+            .flat_map(|&(_, row_index, col_index)| {
+                self.board
+                    .adjacent_positions(row_index, col_index)
+                    .into_iter()
+                    .filter(|to| empty_positions.contains(to))
+                    .map(move |to| MorrisMove::Slide {
+                        from: (row_index, col_index),
+                        to,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        winning_player_on(&self.board)
+    }
+
+    pub fn repeated(&self) -> bool {
+        self.seen.values().any(|&count| count >= REPETITION_LIMIT)
+    }
+
+    pub fn validate_move(&self, player: &Player, morris_move: MorrisMove) -> Result<(), MoveError> {
+        let (to_row, to_col) = morris_move.destination();
+        let destination = self.board.get_cell_at(to_row, to_col)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        match (morris_move, self.is_placing(player)) {
+            (MorrisMove::Place(_), false) => return Err(MoveError::AllPiecesPlaced),
+            (MorrisMove::Slide { .. }, true) => return Err(MoveError::PiecesLeftToPlace),
+            (MorrisMove::Slide { from, to }, false) => {
+                if self.board.get_cell_at(from.0, from.1)? != &Cell::Marker(player.clone()) {
+                    return Err(MoveError::NotYourPiece(from));
+                }
+                if !self.board.adjacent_positions(from.0, from.1).contains(&to) {
+                    return Err(MoveError::NotAdjacent { from, to });
+                }
+            }
+            (MorrisMove::Place(_), true) => {}
+        }
+
+        check_empty(destination, to_row, to_col)
+    }
+}
+
+impl Rules for Morris {
+    type Move = MorrisMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Morris
+    }
+
+    // Once the pieces are all down, marker counts stop changing, so turns go
+    // by how many moves have been played.
+    fn current_player(&self) -> Player {
+        if self.moves_played.is_multiple_of(2) {
+            self.first_player.clone()
+        } else {
+            self.first_player.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<MorrisMove> {
+        self.moves_for(&self.current_player())
+    }
+
+    fn play(&mut self, player: &Player, morris_move: MorrisMove) -> Result<(), MoveError> {
+        self.validate_move(player, morris_move)?;
+        self.apply(player, morris_move);
+        Ok(())
+    }
+
+    // Counts the new position too, so the search sees repetitions along the
+    // line it's looking at.
+
+    fn apply(&mut self, player: &Player, morris_move: MorrisMove) {
+        if let MorrisMove::Slide { from, .. } = morris_move {
+            self.board.rows[from.0].cells[from.1] = Cell::Empty;
+        }
+        let (to_row, to_col) = morris_move.destination();
+        self.board.rows[to_row].cells[to_col] = Cell::Marker(player.clone());
+        self.moves_played += 1;
+
+        let key = position_string(&self.board, &self.current_player());
+        *Rc::make_mut(&mut self.seen).entry(key).or_insert(0) += 1;
+    }
+
+    // A player who can't move loses; that can only happen while sliding.
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::ThreeInARow));
+        }
+        if self.repeated() {
+            return Some(Outcome::draw(Termination::Repetition));
+        }
+        if self.legal_moves().is_empty() {
+            let winner = self.current_player().opponent();
+            return Some(Outcome::win(winner, Termination::NoMovesLeft));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::notation::read_position;
+
+    // A game past its placing phase, with the position's side to move.
+    fn sliding(position: &str) -> Morris {
+        let (board, side_to_move) = read_position(position).unwrap();
+        Morris {
+            board,
+            first_player: side_to_move,
+            moves_played: 2 * PIECES_PER_PLAYER,
+            seen: Rc::new(HashMap::new()),
+        }
+    }
+
+    fn slide(from: (usize, usize), to: (usize, usize)) -> MorrisMove {
+        MorrisMove::Slide { from, to }
+    }
+
+    #[test]
+    fn pieces_only_slide_along_drawn_lines() {
+        let mut morris = sliding("XOX/O__/_XO x");
+        assert_eq!(
+            morris.play(&Player::X, MorrisMove::Place((1, 1))),
+            Err(MoveError::AllPiecesPlaced)
+        );
+        assert_eq!(
+            morris.play(&Player::X, slide((0, 1), (1, 1))),
+            Err(MoveError::NotYourPiece((0, 1)))
+        );
+        assert_eq!(
+            morris.play(&Player::X, slide((2, 1), (1, 2))),
+            Err(MoveError::NotAdjacent {
+                from: (2, 1),
+                to: (1, 2)
+            })
+        );
+        assert_eq!(morris.play(&Player::X, slide((0, 0), (1, 1))), Ok(()));
+        assert_eq!(morris.board.rows[1].cells[1], Cell::Marker(Player::X));
+    }
+
+    #[test]
+    fn the_third_time_a_position_comes_up_is_a_draw() {
+        let mut played = sliding("XOX/O__/_XO x");
+        let mut searched = played.clone();
+        let shuffle = [
+            (Player::X, slide((0, 2), (1, 2))),
+            (Player::O, slide((1, 0), (2, 0))),
+            (Player::X, slide((1, 2), (0, 2))),
+            (Player::O, slide((2, 0), (1, 0))),
+        ];
+
+        for (player, morris_move) in shuffle.iter().cycle().take(8) {
+            played.play(player, *morris_move).unwrap();
+            searched.apply(player, *morris_move);
+        }
+        assert_eq!(played.outcome(), None);
+
+        let (player, morris_move) = &shuffle[0];
+        played.play(player, *morris_move).unwrap();
+        searched.apply(player, *morris_move);
+        let draw = Some(Outcome::draw(Termination::Repetition));
+        assert_eq!(played.outcome(), draw);
+        assert_eq!(searched.outcome(), draw);
+    }
+
+    #[test]
+    fn a_player_who_cannot_slide_loses() {
+        // Three pieces each always leave a move on the open board, so a
+        // knocked-out cell is what corners X here.
+        let mut morris = sliding("XXO/XOO/___ x");
+        morris.board.rows[2].cells[0] = Cell::Blocked;
+
+        assert_eq!(morris.legal_moves(), vec![]);
+        assert_eq!(
+            morris.outcome(),
+            Some(Outcome::win(Player::O, Termination::NoMovesLeft))
+        );
+    }
+}
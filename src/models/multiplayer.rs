@@ -0,0 +1,162 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, winning_player_on};
+use crate::utils::validation::check_empty;
+
+pub const MIN_PLAYERS: usize = 3;
+pub const MAX_PLAYERS: usize = 4;
+pub const DEFAULT_PLAYER_COUNT: usize = 3;
+
+pub type Position = (usize, usize);
+
+// K in a row for any number of seats on any board, which also covers Shaped
+// and, with two stones a turn, Connect6.
+#[derive(Debug, Clone)]
+pub struct Multiplayer {
+    pub board: Board,
+    pub player_count: usize,
+    pub schedule: TurnSchedule,
+    variant: Variant,
+    first_player: Player,
+}
+
+impl Multiplayer {
+    // Any board will do, holes and all.
+    pub fn on_board(
+        variant: Variant,
+        board: Board,
+        player_count: usize,
+        first_player: &Player,
+    ) -> Self {
+        Self {
+            board,
+            player_count,
+            schedule: TurnSchedule::ALTERNATING,
+            variant,
+            first_player: first_player.clone(),
+        }
+    }
+
+    pub fn with_schedule(self, schedule: TurnSchedule) -> Self {
+        Self { schedule, ..self }
+    }
+
+    pub fn marks_placed(&self) -> usize {
+        self.board
+            .get_cells()
+            .iter()
+            .filter(|cell| matches!(cell, Cell::Marker(_)))
+            .count()
+    }
+
+    // How many more marks the current player places this turn, which may be
+    // fewer than the schedule says once the board is nearly full.
+    pub fn marks_left_in_turn(&self) -> usize {
+        let open_cells = self.board.open_cell_count() - self.marks_placed();
+        self.schedule
+            .marks_left_in_turn(self.marks_placed())
+            .min(open_cells)
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        winning_player_on(&self.board)
+    }
+}
+
+impl Rules for Multiplayer {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    // Turns go round the table from the first player, each placing as many
+    // marks as the schedule says.
+    fn current_player(&self) -> Player {
+        self.schedule
+            .mover(self.marks_placed(), &self.first_player, self.player_count)
+    }
+
+    fn legal_moves(&self) -> Vec<Position> {
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, position: Position) -> Result<(), MoveError> {
+        let (row_index, col_index) = position;
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        check_empty(cell, row_index, col_index)?;
+
+        self.apply(player, position);
+        Ok(())
+    }
+
+    fn apply(&mut self, player: &Player, (row_index, col_index): Position) {
+        self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::LineCompleted));
+        }
+        if board_full(&self.board) {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::board::BoardSize;
+
+    #[test]
+    fn turns_go_round_the_table_from_the_first_player() {
+        assert_eq!(Player::O.next(3), Player::Y);
+        assert_eq!(Player::Y.next(3), Player::X);
+        assert_eq!(Player::X.next(3), Player::O);
+        assert_eq!(Player::Z.next(4), Player::X);
+
+        let mut multiplayer = Multiplayer::on_board(
+            Variant::Multiplayer,
+            Board::with_size(BoardSize::MULTIPLAYER),
+            3,
+            &Player::O,
+        );
+        let mut movers = vec![];
+        for col_index in 0..4 {
+            let player = multiplayer.current_player();
+            multiplayer.play(&player, (0, col_index)).unwrap();
+            movers.push(player);
+        }
+        assert_eq!(movers, vec![Player::O, Player::Y, Player::X, Player::O]);
+        assert!(matches!(
+            multiplayer.play(&Player::X, (1, 0)),
+            Err(MoveError::WrongPlayer { .. })
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::utils::ai::winning_symbol_on;
+
+pub const DEFAULT_BOARD_COUNT: usize = 3;
+
+// Which board, then the row and column within it.
+pub type NotaktoMove = (usize, usize, usize);
+
+#[derive(Debug, Clone)]
+pub struct Notakto {
+    pub boards: Vec<Board>,
+    first_player: Player,
+}
+
+impl Notakto {
+    pub fn new(board_count: usize, first_player: &Player) -> Self {
+        Self {
+            boards: vec![Board::new(); board_count],
+            first_player: first_player.clone(),
+        }
+    }
+
+    // A board with three in a row is dead: nobody can play on it again.
+    pub fn is_dead(&self, board_index: usize) -> bool {
+        winning_symbol_on(&self.boards[board_index]).is_some()
+    }
+
+    pub fn live_boards(&self) -> Vec<usize> {
+        (0..self.boards.len())
+            .filter(|&board_index| !self.is_dead(board_index))
+            .collect()
+    }
+
+    pub fn marks_placed(&self) -> usize {
+        self.boards
+            .iter()
+            .flat_map(|board| board.get_cells())
+            .filter(|cell| **cell != Cell::Empty)
+            .count()
+    }
+}
+
+impl Rules for Notakto {
+    type Move = NotaktoMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Notakto
+    }
+
+    // Everyone places an X, so turns go by how many marks are down.
+    fn current_player(&self) -> Player {
+        if self.marks_placed().is_multiple_of(2) {
+            self.first_player.clone()
+        } else {
+            self.first_player.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<NotaktoMove> {
+        self.live_boards()
+            .into_iter()
+            .flat_map(|board_index| {
+                self.boards[board_index]
+                    .get_cell_positions()
+                    .into_iter()
+                    .filter(|(cell, _, _)| **cell == Cell::Empty)
+                    .map(move |(_, row_index, col_index)| (board_index, row_index, col_index))
+            })
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, notakto_move: NotaktoMove) -> Result<(), MoveError> {
+        let (board_index, row_index, col_index) = notakto_move;
+        let board = self
+            .boards
+            .get(board_index)
+            .ok_or(MoveError::NoSuchBoard(board_index))?;
+        let cell = board.get_cell_at(row_index, col_index)?;
+
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        if self.is_dead(board_index) {
+            return Err(MoveError::BoardDead(board_index));
+        }
+
+        if let Cell::Symbol(present) = cell {
+            return Err(MoveError::SymbolPresent {
+                row_index,
+                col_index,
+                symbol: *present,
+            });
+        }
+
+        self.apply(player, notakto_move);
+        Ok(())
+    }
+
+    fn apply(&mut self, _player: &Player, (board_index, row_index, col_index): NotaktoMove) {
+        self.boards[board_index].rows[row_index].cells[col_index] = Cell::Symbol(Symbol::X);
+    }
+
+    // Nobody can draw: the player to move wins once the other one has
+    // killed the last board.
+    fn outcome(&self) -> Option<Outcome> {
+        if self.live_boards().is_empty() {
+            return Some(Outcome::win(
+                self.current_player(),
+                Termination::AllBoardsDead,
+            ));
+        }
+        None
+    }
+}
@@ -0,0 +1,205 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, line_where, sums_to};
+use crate::utils::numerical_ai::evaluate;
+use crate::utils::validation::check_empty;
+
+pub const TARGET_SUM: u8 = 15;
+
+const ODD_NUMBERS: [u8; 5] = [1, 3, 5, 7, 9];
+const EVEN_NUMBERS: [u8; 4] = [2, 4, 6, 8];
+
+// The number and the cell it goes on.
+pub type NumberedMove = (u8, usize, usize);
+
+// Nobody owns a line here: whoever puts down the number that brings a full
+// line to fifteen wins. The first player has the odd numbers and the other
+// the even ones, each to be used once.
+#[derive(Debug, Clone)]
+pub struct Numerical {
+    pub board: Board,
+    pub odd: Player,
+}
+
+impl Numerical {
+    pub fn new(odd: &Player) -> Self {
+        Self {
+            board: Board::new(),
+            odd: odd.clone(),
+        }
+    }
+
+    pub fn numbers_for(&self, player: &Player) -> &'static [u8] {
+        if player == &self.odd {
+            &ODD_NUMBERS
+        } else {
+            &EVEN_NUMBERS
+        }
+    }
+
+    pub fn remaining(&self, player: &Player) -> Vec<u8> {
+        let placed = self.board.get_cells();
+        self.numbers_for(player)
+            .iter()
+            .filter(|&&number| !placed.contains(&&Cell::Number(number)))
+            .copied()
+            .collect()
+    }
+
+    fn numbers_placed(&self) -> usize {
+        self.board
+            .get_cells()
+            .iter()
+            .filter(|cell| matches!(cell, Cell::Number(_)))
+            .count()
+    }
+
+    pub fn winning_line(&self) -> Option<Vec<(usize, usize)>> {
+        line_where(&self.board, |cells| sums_to(cells, TARGET_SUM))
+    }
+
+    // The line is won by whoever finished it, which is the last to move.
+    pub fn winner(&self) -> Option<Player> {
+        self.winning_line()
+            .map(|_| self.current_player().opponent())
+    }
+}
+
+impl Rules for Numerical {
+    type Move = NumberedMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Numerical
+    }
+
+    fn current_player(&self) -> Player {
+        if self.numbers_placed().is_multiple_of(2) {
+            self.odd.clone()
+        } else {
+            self.odd.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<NumberedMove> {
+        let numbers = self.remaining(&self.current_player());
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .flat_map(|&(_, row_index, col_index)| {
+                numbers
+                    .iter()
+                    .map(move |&number| (number, row_index, col_index))
+            })
+            .collect()
+    }
+
+    fn play(
+        &mut self,
+        player: &Player,
+        (number, row_index, col_index): NumberedMove,
+    ) -> Result<(), MoveError> {
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        if !self.remaining(player).contains(&number) {
+            return Err(MoveError::NumberUnavailable(number));
+        }
+
+        let cell = self.board.get_cell_at(row_index, col_index)?;
+        check_empty(cell, row_index, col_index)?;
+
+        self.apply(player, (number, row_index, col_index));
+        Ok(())
+    }
+
+    fn apply(&mut self, _player: &Player, (number, row_index, col_index): NumberedMove) {
+        self.board.rows[row_index].cells[col_index] = Cell::Number(number);
+    }
+
+    // A player with no numbers left to play ends the game in a draw.
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::LineCompleted));
+        }
+        if board_full(&self.board) || self.remaining(&self.current_player()).is_empty() {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+        None
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        evaluate(self, player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_first_player_has_the_odd_numbers() {
+        let mut numerical = Numerical::new(&Player::O);
+        assert_eq!(numerical.numbers_for(&Player::O), &ODD_NUMBERS);
+        assert_eq!(numerical.numbers_for(&Player::X), &EVEN_NUMBERS);
+        assert_eq!(numerical.current_player(), Player::O);
+
+        assert_eq!(
+            numerical.play(&Player::O, (2, 0, 0)),
+            Err(MoveError::NumberUnavailable(2))
+        );
+        assert_eq!(numerical.play(&Player::O, (9, 0, 0)), Ok(()));
+        assert_eq!(
+            numerical.play(&Player::X, (3, 1, 1)),
+            Err(MoveError::NumberUnavailable(3))
+        );
+    }
+
+    #[test]
+    fn a_number_can_only_be_used_once() {
+        let mut numerical = Numerical::new(&Player::X);
+        numerical.play(&Player::X, (5, 0, 0)).unwrap();
+        numerical.play(&Player::O, (2, 1, 1)).unwrap();
+
+        assert_eq!(numerical.remaining(&Player::X), vec![1, 3, 7, 9]);
+        assert_eq!(
+            numerical.play(&Player::X, (5, 2, 2)),
+            Err(MoveError::NumberUnavailable(5))
+        );
+    }
+
+    #[test]
+    fn whoever_brings_a_full_line_to_fifteen_wins() {
+        let mut numerical = Numerical::new(&Player::X);
+        for (player, numbered_move) in [
+            (Player::X, (7, 0, 0)),
+            (Player::O, (2, 1, 1)),
+            (Player::X, (5, 0, 1)),
+            (Player::O, (4, 2, 2)),
+        ] {
+            numerical.play(&player, numbered_move).unwrap();
+        }
+        assert_eq!(numerical.outcome(), None);
+
+        numerical.play(&Player::X, (3, 0, 2)).unwrap();
+        assert_eq!(numerical.winning_line(), Some(vec![(0, 0), (0, 1), (0, 2)]));
+        assert_eq!(
+            numerical.outcome(),
+            Some(Outcome::win(Player::X, Termination::LineCompleted))
+        );
+    }
+}
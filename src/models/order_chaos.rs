@@ -0,0 +1,224 @@
+use crate::error::MoveError;
+use crate::models::board::{Board, BoardSize};
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::models::wild::WildMove;
+use crate::utils::ai::board_full;
+use crate::utils::lines::Lines;
+use crate::utils::order_chaos_ai::{evaluate, ordered_positions, score_for};
+use crate::utils::validation::play_wild_move;
+use std::rc::Rc;
+
+// Five of a kind in a row, exactly: a sixth of the same symbol running on
+// from either end spoils it, so six in a row doesn't count.
+pub fn five_in_a_row(board: &Board) -> Option<Vec<(usize, usize)>> {
+    board.get_cell_position_lines().iter().find_map(|line| {
+        let (first, _, _) = line.first()?;
+        if !matches!(first, Cell::Symbol(_)) || line.iter().any(|(cell, _, _)| cell != first) {
+            return None;
+        }
+
+        let positions: Vec<(usize, usize)> = line
+            .iter()
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect();
+        let (start, next, end) = (positions[0], positions[1], positions[positions.len() - 1]);
+        let step = (
+            next.0 as isize - start.0 as isize,
+            next.1 as isize - start.1 as isize,
+        );
+        let runs_on = |(row_index, col_index): (usize, usize), sign: isize| {
+            let row = row_index as isize + step.0 * sign;
+            let col = col_index as isize + step.1 * sign;
+            row >= 0
+                && col >= 0
+                && board
+                    .get_cell_at(row as usize, col as usize)
+                    .is_ok_and(|cell| cell == *first)
+        };
+
+        if runs_on(start, -1) || runs_on(end, 1) {
+            None
+        } else {
+            Some(positions)
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Order,
+    Chaos,
+}
+
+impl Role {
+    pub fn name(&self) -> &str {
+        match self {
+            Role::Order => "Order",
+            Role::Chaos => "Chaos",
+        }
+    }
+}
+
+// Both players place either symbol, so X and O only name the seats. Order
+// always moves first and wants exactly five of a kind in a row; Chaos wants
+// the board to fill up without that happening.
+#[derive(Debug, Clone)]
+pub struct OrderChaos {
+    pub board: Board,
+    pub order: Player,
+    to_move: Player,
+    // Shared between the copies the search makes, since the board's shape
+    // never changes.
+    lines: Rc<Lines>,
+}
+
+impl OrderChaos {
+    pub fn new(order: &Player) -> Self {
+        let board = Board::with_size(BoardSize::ORDER_AND_CHAOS);
+        Self {
+            lines: Rc::new(Lines::of(&board)),
+            board,
+            order: order.clone(),
+            to_move: order.clone(),
+        }
+    }
+
+    pub fn role_of(&self, player: &Player) -> Role {
+        if player == &self.order {
+            Role::Order
+        } else {
+            Role::Chaos
+        }
+    }
+
+    pub fn player_for(&self, role: Role) -> Player {
+        match role {
+            Role::Order => self.order.clone(),
+            Role::Chaos => self.order.opponent(),
+        }
+    }
+
+    // Five of either symbol is Order's, whoever happened to finish it.
+    pub fn winning_role(&self) -> Option<Role> {
+        if five_in_a_row(&self.board).is_some() {
+            Some(Role::Order)
+        } else if board_full(&self.board) {
+            Some(Role::Chaos)
+        } else {
+            None
+        }
+    }
+}
+
+impl Rules for OrderChaos {
+    type Move = WildMove;
+
+    fn variant(&self) -> Variant {
+        Variant::OrderChaos
+    }
+
+    fn current_player(&self) -> Player {
+        self.to_move.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<WildMove> {
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, _, _)| **cell == Cell::Empty)
+            .flat_map(|&(_, row_index, col_index)| {
+                Symbol::ALL.map(|symbol| (symbol, row_index, col_index))
+            })
+            .collect()
+    }
+
+    fn play(
+        &mut self,
+        player: &Player,
+        (symbol, row_index, col_index): WildMove,
+    ) -> Result<(), MoveError> {
+        if self.winning_role().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        play_wild_move(
+            &mut self.board,
+            &self.to_move,
+            player,
+            symbol,
+            row_index,
+            col_index,
+        )?;
+        self.to_move = self.to_move.opponent();
+        Ok(())
+    }
+
+    fn apply(&mut self, _player: &Player, (symbol, row_index, col_index): WildMove) {
+        self.board.rows[row_index].cells[col_index] = Cell::Symbol(symbol);
+        self.to_move = self.to_move.opponent();
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        self.winning_role().map(|role| {
+            let termination = match role {
+                Role::Order => Termination::LineCompleted,
+                Role::Chaos => Termination::BoardFull,
+            };
+            Outcome::win(self.player_for(role), termination)
+        })
+    }
+
+    fn evaluate(&self, player: &Player) -> i32 {
+        score_for(self.role_of(player), evaluate(&self.board, &self.lines))
+    }
+
+    fn candidate_moves(&self) -> Vec<WildMove> {
+        ordered_positions(&self.board, &self.lines)
+            .into_iter()
+            .flat_map(|(row_index, col_index)| {
+                Symbol::ALL.map(|symbol| (symbol, row_index, col_index))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_with(marks: &[(usize, usize, Symbol)]) -> Board {
+        let mut board = Board::with_size(BoardSize::ORDER_AND_CHAOS);
+        for (row_index, col_index, symbol) in marks {
+            board.rows[*row_index].cells[*col_index] = Cell::Symbol(*symbol);
+        }
+        board
+    }
+
+    #[test]
+    fn five_of_a_kind_wins_for_order() {
+        let marks: Vec<(usize, usize, Symbol)> = (1..6).map(|col| (2, col, Symbol::O)).collect();
+        assert_eq!(
+            five_in_a_row(&board_with(&marks)),
+            Some(vec![(2, 1), (2, 2), (2, 3), (2, 4), (2, 5)])
+        );
+    }
+
+    #[test]
+    fn six_of_a_kind_does_not_count() {
+        let marks: Vec<(usize, usize, Symbol)> = (0..6).map(|row| (row, 0, Symbol::X)).collect();
+        assert_eq!(five_in_a_row(&board_with(&marks)), None);
+    }
+
+    #[test]
+    fn the_other_symbol_next_to_five_does_not_spoil_it() {
+        let mut marks: Vec<(usize, usize, Symbol)> =
+            (0..5).map(|index| (index, index, Symbol::X)).collect();
+        marks.push((5, 5, Symbol::O));
+        assert!(five_in_a_row(&board_with(&marks)).is_some());
+    }
+}
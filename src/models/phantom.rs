@@ -0,0 +1,163 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::{current_player, game_over, winning_player_on};
+use crate::utils::validation::check_empty;
+
+pub type Position = (usize, usize);
+
+// Whose view of the board is drawn while the game is on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Viewer {
+    // Whoever's turn it is, when two people share the keyboard.
+    Turn,
+    // The human's seat, against the computer.
+    Seat(Player),
+    // Nothing is hidden, as in a replay.
+    Everyone,
+}
+
+// Classic tic-tac-toe where neither player sees the other's marks. Each
+// player only knows their own marks and the ones they've bumped into.
+#[derive(Debug, Clone)]
+pub struct Phantom {
+    pub board: Board,
+    pub viewer: Viewer,
+    first_player: Player,
+    found: [Vec<Position>; 2],
+    // The other player's mark the player to move just bumped into, if their
+    // last try found one rather than placing a mark.
+    just_found: Option<Position>,
+}
+
+impl Phantom {
+    pub fn new(first_player: &Player, viewer: Viewer) -> Self {
+        Self {
+            board: Board::new(),
+            viewer,
+            first_player: first_player.clone(),
+            found: [vec![], vec![]],
+            just_found: None,
+        }
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        winning_player_on(&self.board)
+    }
+
+    pub fn just_found(&self) -> Option<Position> {
+        self.just_found
+    }
+
+    pub fn found_by(&self, player: &Player) -> &[Position] {
+        &self.found[player.index()]
+    }
+
+    // The board as this player knows it.
+    pub fn view(&self, player: &Player) -> Board {
+        let mut view = Board::new();
+        for (cell, row_index, col_index) in self.board.get_cell_positions() {
+            let known = match cell {
+                Cell::Marker(mark) if mark == player => true,
+                Cell::Marker(_) => self.found_by(player).contains(&(row_index, col_index)),
+                Cell::Empty | Cell::Blocked | Cell::Number(_) | Cell::Symbol(_) => false,
+            };
+            if known {
+                view.rows[row_index].cells[col_index] = cell.clone();
+            }
+        }
+        view
+    }
+
+    // How many of the other player's marks this player hasn't found yet.
+    pub fn hidden_count(&self, player: &Player) -> usize {
+        let opponent = Cell::Marker(player.opponent());
+        let placed = self
+            .board
+            .get_cells()
+            .iter()
+            .filter(|cell| ***cell == opponent)
+            .count();
+        placed - self.found_by(player).len()
+    }
+}
+
+impl Rules for Phantom {
+    type Move = Position;
+
+    fn variant(&self) -> Variant {
+        Variant::Phantom
+    }
+
+    // Finding a mark doesn't put one down, so the same player goes again.
+    fn current_player(&self) -> Player {
+        current_player(&self.board, &self.first_player)
+    }
+
+    fn legal_moves(&self) -> Vec<Position> {
+        let player = self.current_player();
+        self.board
+            .get_cell_positions()
+            .iter()
+            .filter(|(cell, row_index, col_index)| match cell {
+                Cell::Empty => true,
+                Cell::Marker(by) => {
+                    by != &player && !self.found_by(&player).contains(&(*row_index, *col_index))
+                }
+                Cell::Blocked | Cell::Number(_) | Cell::Symbol(_) => false,
+            })
+            .map(|&(_, row_index, col_index)| (row_index, col_index))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, position: Position) -> Result<(), MoveError> {
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+
+        let (row_index, col_index) = position;
+        // The other player's marks don't count as taken until they're found.
+        match self.board.get_cell_at(row_index, col_index)? {
+            Cell::Marker(by) if by != player && !self.found_by(player).contains(&position) => {}
+            cell => check_empty(cell, row_index, col_index)?,
+        }
+
+        self.apply(player, position);
+        Ok(())
+    }
+
+    // On the other player's mark, the player learns it's there and tries
+    // again.
+    fn apply(&mut self, player: &Player, position: Position) {
+        let (row_index, col_index) = position;
+        if let Cell::Marker(_) = self.board.rows[row_index].cells[col_index] {
+            self.found[player.index()].push(position);
+            self.just_found = Some(position);
+        } else {
+            self.board.rows[row_index].cells[col_index] = Cell::Marker(player.clone());
+            self.just_found = None;
+        }
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::ThreeInARow));
+        }
+        if game_over(&self.board) {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+        None
+    }
+}
@@ -1,21 +1,56 @@
-#[derive(Debug, PartialEq, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Player {
     X,
     O,
+    Y,
+    Z,
 }
 
 impl Player {
+    // Seats in turn order. Two-player games only ever use the first two.
+    pub const ALL: [Player; 4] = [Player::X, Player::O, Player::Y, Player::Z];
+
     pub fn character(&self) -> &str {
         match self {
             Player::X => "X",
             Player::O => "O",
+            Player::Y => "Y",
+            Player::Z => "Z",
         }
     }
 
-    pub fn opponent(&self) -> Player {
+    pub fn from_character(character: char) -> Option<Player> {
+        Player::ALL
+            .iter()
+            .find(|player| {
+                player
+                    .character()
+                    .eq_ignore_ascii_case(&character.to_string())
+            })
+            .cloned()
+    }
+
+    pub fn index(&self) -> usize {
         match self {
-            Player::X => Player::O,
-            Player::O => Player::X,
+            Player::X => 0,
+            Player::O => 1,
+            Player::Y => 2,
+            Player::Z => 3,
         }
     }
+
+    pub fn seats(player_count: usize) -> &'static [Player] {
+        &Player::ALL[..player_count]
+    }
+
+    // Whoever plays after this player when turns go round the table.
+    pub fn next(&self, player_count: usize) -> Player {
+        Player::ALL[(self.index() + 1) % player_count].clone()
+    }
+
+    pub fn opponent(&self) -> Player {
+        self.next(2)
+    }
 }
@@ -0,0 +1,494 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::win_arrangements;
+use crate::utils::quantum_ai::spooky_pairs;
+
+pub type Position = (usize, usize);
+
+// A move in quantum tic-tac-toe: one mark in two cells at once until a
+// measurement decides which. `number` is the move number the mark is written
+// with, as in X1, O2, X3...
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpookyMark {
+    pub player: Player,
+    pub number: usize,
+    pub cells: (Position, Position),
+    pub landed: Option<Position>,
+}
+
+// A spooky mark across two cells, a classical one when only one cell is left,
+// or where the mark that closed a cycle lands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantumMove {
+    Spooky(Position, Position),
+    Classical(Position),
+    Collapse(Position),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantumOutcome {
+    pub winner: Option<Player>,
+    // Both players completed lines in the same collapse; the one whose line
+    // was finished later only scores half.
+    pub runner_up: Option<Player>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Quantum {
+    pub marks: Vec<SpookyMark>,
+    pub pending_collapse: Option<usize>,
+    first_player: Player,
+}
+
+impl SpookyMark {
+    pub fn touches(&self, position: Position) -> bool {
+        self.cells.0 == position || self.cells.1 == position
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}{}", self.player.character(), self.number)
+    }
+}
+
+impl Quantum {
+    pub fn new(first_player: &Player) -> Self {
+        Self {
+            marks: vec![],
+            pending_collapse: None,
+            first_player: first_player.clone(),
+        }
+    }
+
+    pub fn classical_mark(&self, position: Position) -> Option<&SpookyMark> {
+        self.marks.iter().find(|mark| mark.landed == Some(position))
+    }
+
+    pub fn spooky_marks(&self, position: Position) -> Vec<&SpookyMark> {
+        self.marks
+            .iter()
+            .filter(|mark| mark.landed.is_none() && mark.touches(position))
+            .collect()
+    }
+
+    pub fn open_positions(&self) -> Vec<Position> {
+        (0..3)
+            .flat_map(|row_index| (0..3).map(move |col_index| (row_index, col_index)))
+            .filter(|&position| self.classical_mark(position).is_none())
+            .collect()
+    }
+
+    // With a single open cell left there's nowhere to entangle with, so the
+    // last mark goes down classically.
+    pub fn needs_classical_move(&self) -> bool {
+        self.open_positions().len() == 1
+    }
+
+    // The player who closed a cycle doesn't get to pick how it collapses.
+    pub fn collapse_chooser(&self) -> Option<Player> {
+        self.pending_collapse
+            .map(|mark_index| self.marks[mark_index].player.opponent())
+    }
+
+    // The board as far as measurement has settled it.
+    pub fn classical_board(&self) -> Board {
+        let mut board = Board::new();
+        for mark in &self.marks {
+            if let Some((row_index, col_index)) = mark.landed {
+                board.rows[row_index].cells[col_index] = Cell::Marker(mark.player.clone());
+            }
+        }
+        board
+    }
+
+    fn connected(&self, from: Position, to: Position) -> bool {
+        let mut reached = vec![from];
+        let mut frontier = vec![from];
+
+        while let Some(position) = frontier.pop() {
+            for mark in self.spooky_marks(position) {
+                let other = if mark.cells.0 == position {
+                    mark.cells.1
+                } else {
+                    mark.cells.0
+                };
+                if !reached.contains(&other) {
+                    reached.push(other);
+                    frontier.push(other);
+                }
+            }
+        }
+
+        reached.contains(&to)
+    }
+
+    pub fn quantum_outcome(&self) -> Option<QuantumOutcome> {
+        if self.pending_collapse.is_some() {
+            return None;
+        }
+
+        let board = self.classical_board();
+        // For each player, the earliest a line of theirs was completed: the
+        // highest move number in the line.
+        let finished_at = |player: &Player| {
+            win_arrangements(&board)
+                .iter()
+                .filter(|line| {
+                    line.iter()
+                        .all(|(cell, _, _)| **cell == Cell::Marker(player.clone()))
+                })
+                .filter_map(|line| {
+                    line.iter()
+                        .filter_map(|&(_, row_index, col_index)| {
+                            self.classical_mark((row_index, col_index))
+                                .map(|mark| mark.number)
+                        })
+                        .max()
+                })
+                .min()
+        };
+
+        match (finished_at(&Player::X), finished_at(&Player::O)) {
+            (None, None) if self.open_positions().is_empty() => Some(QuantumOutcome {
+                winner: None,
+                runner_up: None,
+            }),
+            (None, None) => None,
+            (Some(_), None) => Some(QuantumOutcome {
+                winner: Some(Player::X),
+                runner_up: None,
+            }),
+            (None, Some(_)) => Some(QuantumOutcome {
+                winner: Some(Player::O),
+                runner_up: None,
+            }),
+            (Some(x_at), Some(o_at)) => {
+                let (winner, runner_up) = if x_at < o_at {
+                    (Player::X, Player::O)
+                } else {
+                    (Player::O, Player::X)
+                };
+                Some(QuantumOutcome {
+                    winner: Some(winner),
+                    runner_up: Some(runner_up),
+                })
+            }
+        }
+    }
+
+    fn check_turn(&self, player: &Player) -> Result<(), MoveError> {
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+        if self.pending_collapse.is_some() {
+            return Err(MoveError::CollapsePending);
+        }
+
+        let expected = self.current_player();
+        if &expected != player {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: player.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_open(&self, position: Position) -> Result<(), MoveError> {
+        let (row_index, col_index) = position;
+        if row_index >= 3 || col_index >= 3 {
+            return Err(MoveError::OutOfBounds {
+                row_index,
+                col_index,
+            });
+        }
+
+        match self.classical_mark(position) {
+            Some(mark) => Err(MoveError::Occupied {
+                row_index,
+                col_index,
+                by: mark.player.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // Returns whether the new mark closed a cycle, which has to be collapsed
+    // before play goes on.
+    pub fn play_spooky(
+        &mut self,
+        player: &Player,
+        cells: (Position, Position),
+    ) -> Result<bool, MoveError> {
+        self.check_open(cells.0)?;
+        self.check_open(cells.1)?;
+        self.check_turn(player)?;
+
+        if cells.0 == cells.1 {
+            return Err(MoveError::SameCell(cells.0));
+        }
+        if self.needs_classical_move() {
+            return Err(MoveError::MustPlaceClassically);
+        }
+
+        Ok(self.apply_spooky(player, cells))
+    }
+
+    // Places a spooky mark without checking it and reports whether it closed
+    // a cycle. Used by the AI's look-ahead.
+    pub fn apply_spooky(&mut self, player: &Player, cells: (Position, Position)) -> bool {
+        let closes_cycle = self.connected(cells.0, cells.1);
+        self.marks.push(SpookyMark {
+            player: player.clone(),
+            number: self.marks.len() + 1,
+            cells,
+            landed: None,
+        });
+        if closes_cycle {
+            self.pending_collapse = Some(self.marks.len() - 1);
+        }
+        closes_cycle
+    }
+
+    pub fn play_classical(&mut self, player: &Player, position: Position) -> Result<(), MoveError> {
+        self.check_open(position)?;
+        self.check_turn(player)?;
+
+        if !self.needs_classical_move() {
+            return Err(MoveError::MustPlaceSpooky);
+        }
+
+        self.apply_classical(player, position);
+        Ok(())
+    }
+
+    pub fn apply_classical(&mut self, player: &Player, position: Position) {
+        self.marks.push(SpookyMark {
+            player: player.clone(),
+            number: self.marks.len() + 1,
+            cells: (position, position),
+            landed: Some(position),
+        });
+    }
+
+    pub fn collapse(&mut self, chooser: &Player, position: Position) -> Result<(), MoveError> {
+        let mark_index = self.pending_collapse.ok_or(MoveError::NoCollapsePending)?;
+
+        let expected = self.marks[mark_index].player.opponent();
+        if &expected != chooser {
+            return Err(MoveError::WrongPlayer {
+                expected,
+                attempted: chooser.clone(),
+            });
+        }
+        if !self.marks[mark_index].touches(position) {
+            return Err(MoveError::NotInPair(position));
+        }
+
+        self.apply_collapse(position);
+        Ok(())
+    }
+
+    // Measures the cycle-closing mark into `position`. Every mark sharing a
+    // cell with a settled one is pushed into its other cell, and so on down
+    // the chain.
+    pub fn apply_collapse(&mut self, position: Position) {
+        let Some(mark_index) = self.pending_collapse else {
+            return;
+        };
+
+        let mut settling = vec![(mark_index, position)];
+        while let Some((mark_index, position)) = settling.pop() {
+            if self.marks[mark_index].landed.is_some() {
+                continue;
+            }
+            self.marks[mark_index].landed = Some(position);
+
+            for (other_index, other) in self.marks.iter().enumerate() {
+                if other.landed.is_none() && other.touches(position) {
+                    let elsewhere = if other.cells.0 == position {
+                        other.cells.1
+                    } else {
+                        other.cells.0
+                    };
+                    settling.push((other_index, elsewhere));
+                }
+            }
+        }
+
+        self.pending_collapse = None;
+    }
+}
+
+impl Rules for Quantum {
+    type Move = QuantumMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Quantum
+    }
+
+    // A closed cycle has to be measured before anyone moves again.
+    fn current_player(&self) -> Player {
+        if let Some(chooser) = self.collapse_chooser() {
+            return chooser;
+        }
+
+        if self.marks.len().is_multiple_of(2) {
+            self.first_player.clone()
+        } else {
+            self.first_player.opponent()
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<QuantumMove> {
+        if let Some(mark_index) = self.pending_collapse {
+            let (first, second) = self.marks[mark_index].cells;
+            return vec![QuantumMove::Collapse(first), QuantumMove::Collapse(second)];
+        }
+
+        if self.needs_classical_move() {
+            return self
+                .open_positions()
+                .into_iter()
+                .map(QuantumMove::Classical)
+                .collect();
+        }
+
+        spooky_pairs(self)
+            .into_iter()
+            .map(|(first, second)| QuantumMove::Spooky(first, second))
+            .collect()
+    }
+
+    fn play(&mut self, player: &Player, quantum_move: QuantumMove) -> Result<(), MoveError> {
+        match quantum_move {
+            QuantumMove::Spooky(first, second) => {
+                self.play_spooky(player, (first, second)).map(|_| ())
+            }
+            QuantumMove::Classical(position) => self.play_classical(player, position),
+            QuantumMove::Collapse(position) => self.collapse(player, position),
+        }
+    }
+
+    fn apply(&mut self, player: &Player, quantum_move: QuantumMove) {
+        match quantum_move {
+            QuantumMove::Spooky(first, second) => {
+                self.apply_spooky(player, (first, second));
+            }
+            QuantumMove::Classical(position) => self.apply_classical(player, position),
+            QuantumMove::Collapse(position) => self.apply_collapse(position),
+        }
+    }
+
+    // A split win still goes to whoever finished their line first.
+    fn outcome(&self) -> Option<Outcome> {
+        let outcome = self.quantum_outcome()?;
+        Some(match outcome.winner {
+            Some(winner) => Outcome::win(winner, Termination::ThreeInARow),
+            None => Outcome::draw(Termination::BoardFull),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_classical_mark_waits_for_the_last_open_cell() {
+        let mut quantum = Quantum::new(&Player::X);
+        assert_eq!(
+            quantum.play_classical(&Player::X, (1, 1)),
+            Err(MoveError::MustPlaceSpooky)
+        );
+
+        let filled = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 0),
+        ];
+        for (index, &position) in filled.iter().enumerate() {
+            let player = if index % 2 == 0 { Player::X } else { Player::O };
+            quantum.apply_classical(&player, position);
+        }
+        assert_eq!(quantum.play_classical(&Player::X, (2, 2)), Ok(()));
+    }
+    fn landed(quantum: &Quantum) -> Vec<Option<Position>> {
+        quantum.marks.iter().map(|mark| mark.landed).collect()
+    }
+
+    #[test]
+    fn two_marks_in_the_same_pair_close_a_cycle() {
+        let mut quantum = Quantum::new(&Player::X);
+        assert!(!quantum.apply_spooky(&Player::X, ((0, 0), (0, 1))));
+        assert!(quantum.connected((0, 0), (0, 1)));
+        assert!(quantum.apply_spooky(&Player::O, ((0, 1), (0, 0))));
+        assert_eq!(quantum.collapse_chooser(), Some(Player::X));
+
+        quantum.apply_collapse((0, 0));
+        assert_eq!(landed(&quantum), vec![Some((0, 1)), Some((0, 0))]);
+    }
+
+    #[test]
+    fn three_marks_around_a_triangle_close_a_cycle() {
+        let mut quantum = Quantum::new(&Player::X);
+        assert!(!quantum.apply_spooky(&Player::X, ((0, 0), (0, 1))));
+        assert!(!quantum.apply_spooky(&Player::O, ((0, 1), (1, 1))));
+        assert!(!quantum.connected((0, 0), (2, 2)));
+        assert!(quantum.apply_spooky(&Player::X, ((1, 1), (0, 0))));
+        assert_eq!(quantum.pending_collapse, Some(2));
+    }
+
+    #[test]
+    fn a_collapse_settles_the_tail_hanging_off_the_cycle() {
+        let mut quantum = Quantum::new(&Player::X);
+        quantum.apply_spooky(&Player::X, ((0, 0), (0, 1)));
+        quantum.apply_spooky(&Player::O, ((0, 2), (1, 0)));
+        quantum.apply_spooky(&Player::X, ((0, 1), (0, 2)));
+        assert!(quantum.apply_spooky(&Player::O, ((0, 2), (0, 0))));
+
+        quantum.apply_collapse((0, 0));
+        assert_eq!(
+            landed(&quantum),
+            vec![Some((0, 1)), Some((1, 0)), Some((0, 2)), Some((0, 0))]
+        );
+        assert_eq!(quantum.pending_collapse, None);
+    }
+
+    #[test]
+    fn when_one_collapse_completes_two_lines_the_earlier_one_wins() {
+        let mut quantum = Quantum::new(&Player::X);
+        let pairs = [
+            ((0, 0), (1, 0)),
+            ((1, 0), (0, 1)),
+            ((0, 1), (1, 1)),
+            ((1, 1), (0, 2)),
+            ((0, 2), (1, 2)),
+        ];
+        for (index, &cells) in pairs.iter().enumerate() {
+            let player = if index % 2 == 0 { Player::X } else { Player::O };
+            assert!(!quantum.apply_spooky(&player, cells));
+        }
+        assert!(quantum.apply_spooky(&Player::O, ((1, 2), (0, 0))));
+        assert_eq!(quantum.quantum_outcome(), None);
+
+        // X's row is finished by X5 and O's by O6, so X takes the win.
+        quantum.apply_collapse((1, 2));
+        assert_eq!(
+            quantum.quantum_outcome(),
+            Some(QuantumOutcome {
+                winner: Some(Player::X),
+                runner_up: Some(Player::O),
+            })
+        );
+    }
+}
@@ -2,13 +2,13 @@ use crate::models::cell::Cell;
 
 #[derive(Debug, Clone)]
 pub struct Row {
-    pub cells: [Cell; 3],
+    pub cells: Vec<Cell>,
 }
 
 impl Row {
-    pub fn new() -> Self {
+    pub fn new(width: usize) -> Self {
         Self {
-            cells: [Cell::Empty, Cell::Empty, Cell::Empty],
+            cells: vec![Cell::Empty; width],
         }
     }
 }
@@ -0,0 +1,63 @@
+use crate::error::MoveError;
+use crate::models::game_record::{GameResult, Termination};
+use crate::models::player::Player;
+use crate::models::variant::Variant;
+
+// How a finished game ended: who won, if anyone, and why it stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub result: GameResult,
+    pub termination: Termination,
+}
+
+impl Outcome {
+    pub fn win(winner: Player, termination: Termination) -> Self {
+        Self {
+            result: GameResult::Winner(winner),
+            termination,
+        }
+    }
+
+    pub fn draw(termination: Termination) -> Self {
+        Self {
+            result: GameResult::Draw,
+            termination,
+        }
+    }
+}
+
+// Everything a variant decides on its own: whose turn it is, which moves they
+// have, what a move does, and when and how the game is over. Nothing here
+// draws or asks for anything, so the AI can search through it and any
+// front-end can drive it.
+pub trait Rules: Clone {
+    type Move: Copy + PartialEq;
+
+    fn variant(&self) -> Variant;
+
+    fn current_player(&self) -> Player;
+
+    // The moves open to the player to move, while the game isn't over.
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    fn play(&mut self, player: &Player, game_move: Self::Move) -> Result<(), MoveError>;
+
+    // Makes one of the legal moves without checking it, for the AI's
+    // look-ahead.
+    fn apply(&mut self, player: &Player, game_move: Self::Move);
+
+    // None while the game is still going.
+    fn outcome(&self) -> Option<Outcome>;
+
+    // How good an unfinished position looks for the player, for when the
+    // search stops short of the end. It has to stay well below a win.
+    fn evaluate(&self, _player: &Player) -> i32 {
+        0
+    }
+
+    // The legal moves the search should look at, best first. A variant with
+    // too many to search can leave the weak ones out.
+    fn candidate_moves(&self) -> Vec<Self::Move> {
+        self.legal_moves()
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+// What goes on the board when the marks don't belong to anyone, as in wild
+// tic-tac-toe, Order and Chaos and Notakto. Whose turn it is stays a `Player`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Symbol {
+    X,
+    O,
+}
+
+impl Symbol {
+    pub const ALL: [Symbol; 2] = [Symbol::X, Symbol::O];
+
+    pub fn character(&self) -> &str {
+        match self {
+            Symbol::X => "X",
+            Symbol::O => "O",
+        }
+    }
+
+    pub fn from_character(character: char) -> Option<Symbol> {
+        match character {
+            'X' | 'x' => Some(Symbol::X),
+            'O' | 'o' => Some(Symbol::O),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::models::player::Player;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// How many marks each turn places: the opening turn can differ from every
+// turn after it, as in Connect6 where the first player starts with one stone
+// and everyone places two from then on. Seats take turns round the table.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TurnSchedule {
+    pub first_turn: usize,
+    pub per_turn: usize,
+}
+
+impl TurnSchedule {
+    pub const ALTERNATING: TurnSchedule = TurnSchedule {
+        first_turn: 1,
+        per_turn: 1,
+    };
+
+    pub const CONNECT6: TurnSchedule = TurnSchedule {
+        first_turn: 1,
+        per_turn: 2,
+    };
+
+    // Written as "<first turn>/<every other turn>", like 1/2.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (first_turn, per_turn) = text.trim().split_once('/')?;
+        let schedule = TurnSchedule {
+            first_turn: first_turn.parse().ok()?,
+            per_turn: per_turn.parse().ok()?,
+        };
+        Some(schedule).filter(|schedule| schedule.first_turn > 0 && schedule.per_turn > 0)
+    }
+
+    // Which turn of the game the next mark belongs to, counting from 0.
+    pub fn turn_index(&self, marks_placed: usize) -> usize {
+        if marks_placed < self.first_turn {
+            0
+        } else {
+            1 + (marks_placed - self.first_turn) / self.per_turn
+        }
+    }
+
+    // Marks still to place in the turn the next mark belongs to.
+    pub fn marks_left_in_turn(&self, marks_placed: usize) -> usize {
+        if marks_placed < self.first_turn {
+            self.first_turn - marks_placed
+        } else {
+            self.per_turn - (marks_placed - self.first_turn) % self.per_turn
+        }
+    }
+
+    // Who places the next mark.
+    pub fn mover(&self, marks_placed: usize, first_player: &Player, player_count: usize) -> Player {
+        let seat = (first_player.index() + self.turn_index(marks_placed)) % player_count;
+        Player::seats(player_count)[seat].clone()
+    }
+
+    // Which round of the table the next mark belongs to, for numbering moves.
+    pub fn round(&self, marks_placed: usize, player_count: usize) -> usize {
+        self.turn_index(marks_placed) / player_count
+    }
+}
+
+impl Default for TurnSchedule {
+    fn default() -> Self {
+        TurnSchedule::ALTERNATING
+    }
+}
+
+impl fmt::Display for TurnSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.first_turn, self.per_turn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::player::Player::{O, X, Y};
+
+    #[test]
+    fn one_then_two_marks_a_turn() {
+        let schedule = TurnSchedule::CONNECT6;
+        // Marks placed so far, then the turn, marks left in it and the mover
+        // with two seats and with three.
+        let table = [
+            (0, 0, 1, X, X),
+            (1, 1, 2, O, O),
+            (2, 1, 1, O, O),
+            (3, 2, 2, X, Y),
+            (4, 2, 1, X, Y),
+            (5, 3, 2, O, X),
+            (6, 3, 1, O, X),
+        ];
+
+        for (marks_placed, turn_index, marks_left, two_seats, three_seats) in table {
+            assert_eq!(schedule.turn_index(marks_placed), turn_index);
+            assert_eq!(schedule.marks_left_in_turn(marks_placed), marks_left);
+            assert_eq!(schedule.mover(marks_placed, &X, 2), two_seats);
+            assert_eq!(schedule.mover(marks_placed, &X, 3), three_seats);
+        }
+    }
+
+    #[test]
+    fn schedules_are_read_as_first_turn_over_every_other() {
+        assert_eq!(TurnSchedule::parse(" 1/2 "), Some(TurnSchedule::CONNECT6));
+        assert_eq!(TurnSchedule::parse("0/2"), None);
+        assert_eq!(TurnSchedule::parse("2"), None);
+        assert_eq!(TurnSchedule::parse("a/b"), None);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::models::board::BoardSize;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    #[default]
+    Classic,
+    Misere,
+    Ultimate,
+    Qubic,
+    Gravity,
+    Wild,
+    Notakto,
+    Morris,
+    Infinite,
+    Quantum,
+    OrderChaos,
+    Multiplayer,
+    Shaped,
+    Gomoku,
+    Connect6,
+    Phantom,
+    Numerical,
+    Gobblet,
+    // Defined in a variant file rather than built in.
+    Custom,
+}
+
+impl Variant {
+    // Every built-in variant. Custom ones come from the variant files instead.
+    pub const ALL: [Variant; 18] = [
+        Variant::Classic,
+        Variant::Misere,
+        Variant::Ultimate,
+        Variant::Qubic,
+        Variant::Gravity,
+        Variant::Wild,
+        Variant::Notakto,
+        Variant::Morris,
+        Variant::Infinite,
+        Variant::Quantum,
+        Variant::OrderChaos,
+        Variant::Multiplayer,
+        Variant::Shaped,
+        Variant::Gomoku,
+        Variant::Connect6,
+        Variant::Phantom,
+        Variant::Numerical,
+        Variant::Gobblet,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Variant::Classic => "classic",
+            Variant::Misere => "misere",
+            Variant::Ultimate => "ultimate",
+            Variant::Qubic => "qubic",
+            Variant::Gravity => "gravity",
+            Variant::Wild => "wild",
+            Variant::Notakto => "notakto",
+            Variant::Morris => "morris",
+            Variant::Infinite => "infinite",
+            Variant::Quantum => "quantum",
+            Variant::OrderChaos => "order-chaos",
+            Variant::Multiplayer => "multiplayer",
+            Variant::Shaped => "shaped",
+            Variant::Gomoku => "gomoku",
+            Variant::Connect6 => "connect6",
+            Variant::Phantom => "phantom",
+            Variant::Numerical => "numerical",
+            Variant::Gobblet => "gobblet",
+            Variant::Custom => "custom",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Variant::Classic => "Classic (three in a row wins)",
+            Variant::Misere => "Misère (three in a row loses)",
+            Variant::Ultimate => "Ultimate (a board of boards)",
+            Variant::Qubic => "Qubic (4x4x4 in 3D)",
+            Variant::Gravity => "Gravity (marks drop, like Connect Four)",
+            Variant::Wild => "Wild (place X or O, any line wins)",
+            Variant::Notakto => "Notakto (X only on several boards, last line loses)",
+            Variant::Morris => "Three men's morris (three pieces each, then slide)",
+            Variant::Infinite => "Infinite (your oldest mark vanishes after three)",
+            Variant::Quantum => "Quantum (marks sit in two cells until a cycle collapses)",
+            Variant::OrderChaos => {
+                "Order and Chaos (6x6, Order wants exactly five of a kind, Chaos a full board)"
+            }
+            Variant::Multiplayer => "Multiplayer (three or four players, four in a row on 5x5)",
+            Variant::Shaped => "Shaped (any board shape, with holes and blocked cells)",
+            Variant::Gomoku => "Gomoku (five in a row on 15x15, with swap and Renju options)",
+            Variant::Connect6 => "Connect6 (two stones a turn, six in a row on 19x19)",
+            Variant::Phantom => "Phantom (you can't see the other player's marks)",
+            Variant::Numerical => "Numerical (odd vs even numbers, a line adding up to 15 wins)",
+            Variant::Gobblet => "Gobblet (bigger pieces cover smaller ones, on or off the board)",
+            Variant::Custom => "Custom (defined in a variant file)",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Variant::ALL
+            .iter()
+            .find(|variant| variant.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+
+    pub fn strategy(&self) -> &str {
+        match self {
+            Variant::Classic => "heuristic",
+            Variant::Misere => "minimax",
+            Variant::Ultimate => "alpha-beta",
+            Variant::Qubic => "beam-search",
+            Variant::Gravity => "drop-search",
+            Variant::Wild => "solver",
+            Variant::Notakto => "solver",
+            Variant::Morris => "alpha-beta",
+            Variant::Infinite => "alpha-beta",
+            Variant::Quantum => "collapse-search",
+            Variant::OrderChaos => "alpha-beta",
+            Variant::Multiplayer => "paranoid",
+            Variant::Shaped => "alpha-beta",
+            Variant::Gomoku => "threat-scan",
+            Variant::Connect6 => "threat-scan",
+            Variant::Phantom => "sampling",
+            Variant::Numerical => "alpha-beta",
+            Variant::Gobblet => "alpha-beta",
+            Variant::Custom => "alpha-beta",
+        }
+    }
+
+    pub fn uses_classic_board(&self) -> bool {
+        matches!(self, Variant::Classic | Variant::Misere)
+    }
+
+    pub fn default_board_size(&self) -> BoardSize {
+        match self {
+            Variant::Gravity => BoardSize::CONNECT_FOUR,
+            Variant::OrderChaos => BoardSize::ORDER_AND_CHAOS,
+            Variant::Multiplayer => BoardSize::MULTIPLAYER,
+            Variant::Shaped => BoardSize::SHAPED,
+            Variant::Gomoku => BoardSize::GOMOKU,
+            Variant::Connect6 => BoardSize::CONNECT6,
+            _ => BoardSize::CLASSIC,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::game_record::Termination;
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::utils::ai::{board_full, winning_symbol_on};
+use crate::utils::validation::play_wild_move;
+use crate::utils::wild_ai::wild_moves;
+
+// The symbol and the cell it goes on.
+pub type WildMove = (Symbol, usize, usize);
+
+// Both players place either symbol, and a line of either is won by whoever
+// finished it.
+#[derive(Debug, Clone)]
+pub struct Wild {
+    pub board: Board,
+    to_move: Player,
+}
+
+impl Wild {
+    pub fn new(first_player: &Player) -> Self {
+        Self {
+            board: Board::new(),
+            to_move: first_player.clone(),
+        }
+    }
+
+    // The line goes to whoever moved last, whichever symbol it's made of.
+    pub fn winner(&self) -> Option<Player> {
+        winning_symbol_on(&self.board).map(|_| self.to_move.opponent())
+    }
+}
+
+impl Rules for Wild {
+    type Move = WildMove;
+
+    fn variant(&self) -> Variant {
+        Variant::Wild
+    }
+
+    fn current_player(&self) -> Player {
+        self.to_move.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<WildMove> {
+        wild_moves(&self.board)
+    }
+
+    fn play(
+        &mut self,
+        player: &Player,
+        (symbol, row_index, col_index): WildMove,
+    ) -> Result<(), MoveError> {
+        if self.outcome().is_some() {
+            return Err(MoveError::GameOver);
+        }
+
+        play_wild_move(
+            &mut self.board,
+            &self.to_move,
+            player,
+            symbol,
+            row_index,
+            col_index,
+        )?;
+        self.to_move = self.to_move.opponent();
+        Ok(())
+    }
+
+    fn apply(&mut self, _player: &Player, (symbol, row_index, col_index): WildMove) {
+        self.board.rows[row_index].cells[col_index] = Cell::Symbol(symbol);
+        self.to_move = self.to_move.opponent();
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::win(winner, Termination::ThreeInARow));
+        }
+        if board_full(&self.board) {
+            return Some(Outcome::draw(Termination::BoardFull));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_line_goes_to_whoever_finished_it() {
+        let mut wild = Wild::new(&Player::X);
+        let moves = [
+            (Player::X, Symbol::O, 0, 0),
+            (Player::O, Symbol::X, 1, 1),
+            (Player::X, Symbol::X, 2, 2),
+            (Player::O, Symbol::O, 0, 1),
+            (Player::X, Symbol::O, 0, 2),
+        ];
+        for (player, symbol, row_index, col_index) in moves.iter() {
+            wild.play(player, (*symbol, *row_index, *col_index))
+                .unwrap();
+        }
+
+        assert_eq!(wild.winner(), Some(Player::X));
+        assert_eq!(
+            wild.play(&Player::O, (Symbol::X, 2, 0)),
+            Err(MoveError::GameOver)
+        );
+    }
+
+    #[test]
+    fn turns_alternate_whatever_symbol_is_placed() {
+        let mut wild = Wild::new(&Player::O);
+        wild.play(&Player::O, (Symbol::O, 1, 1)).unwrap();
+
+        assert_eq!(wild.current_player(), Player::X);
+        assert_eq!(
+            wild.play(&Player::O, (Symbol::O, 0, 0)),
+            Err(MoveError::WrongPlayer {
+                expected: Player::X,
+                attempted: Player::O,
+            })
+        );
+        assert_eq!(
+            wild.play(&Player::X, (Symbol::X, 1, 1)),
+            Err(MoveError::SymbolPresent {
+                row_index: 1,
+                col_index: 1,
+                symbol: Symbol::O,
+            })
+        );
+    }
+}
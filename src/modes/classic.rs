@@ -0,0 +1,59 @@
+use crate::error::{Error, MoveError};
+use crate::game::Playable;
+use crate::models::classic::{Classic, Position};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove};
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::best_next_move;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_losing_player;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_cell_position;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for Classic {
+    fn render(&self) {
+        render_board(&self.board);
+    }
+
+    // In misère the winner is whoever didn't make the line, so it's the
+    // loser who gets named.
+    fn render_outcome(&self, outcome: &Outcome) {
+        match (&outcome.result, self.variant()) {
+            (GameResult::Winner(winner), Variant::Misere) => {
+                render_losing_player(&winner.opponent())
+            }
+            (GameResult::Winner(winner), _) => render_winning_player(winner),
+            (GameResult::Draw | GameResult::Unfinished, _) => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        ask_for_cell_position("Which cell? (e.g., A1, C2, etc.)")
+    }
+
+    // Classic keeps its hand-written heuristic; misère has none, so it
+    // searches the rules to the end.
+    fn computer_move(&self, player: &Player) -> Option<Position> {
+        match self.variant() {
+            Variant::Misere => best_rules_move(self, self.legal_moves().len()),
+            _ => best_next_move(&self.board, player)
+                .map(|(_, row_index, col_index)| (row_index, col_index)),
+        }
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
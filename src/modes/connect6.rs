@@ -0,0 +1,85 @@
+use crate::error::Error;
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Seating};
+use crate::models::board::{Board, BoardSize};
+use crate::models::game_record::GameRecord;
+use crate::models::multiplayer::Multiplayer;
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_seats;
+
+// Six in a row with two stones a turn, except for the very first one. Each
+// stone is a move of its own, and the schedule says when the turn passes.
+pub fn play(seating: &Seating, size: BoardSize, options: &GameOptions) -> Result<(), Error> {
+    let mut record = new_record(Variant::Connect6, seating);
+    record.metadata.board_size = Some(size);
+    record.metadata.schedule = Some(TurnSchedule::CONNECT6);
+    let mut connect6 = Multiplayer::on_board(
+        Variant::Connect6,
+        Board::with_size(size),
+        2,
+        &seating.first_player,
+    )
+    .with_schedule(TurnSchedule::CONNECT6);
+
+    next_turn(&mut connect6, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    let metadata = &record.metadata;
+    let size = metadata.board_size.unwrap_or(BoardSize::CONNECT6);
+
+    render_seats(metadata);
+    replay_game(
+        &mut Multiplayer::on_board(
+            Variant::Connect6,
+            Board::with_size(size),
+            2,
+            &metadata.first_player,
+        )
+        .with_schedule(metadata.schedule.unwrap_or(TurnSchedule::CONNECT6)),
+        record,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::take_turn;
+    use crate::models::cell::Cell;
+    use crate::models::player::Player;
+    use crate::models::rules::Rules;
+    use crate::utils::input::use_script;
+
+    #[test]
+    fn both_stones_of_a_turn_come_from_one_line() {
+        let path = std::env::temp_dir().join(format!(
+            "tic-tac-toe-rust-connect6-test-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "J10 K11\n").unwrap();
+        use_script(path.to_str().unwrap()).unwrap();
+
+        let mut connect6 = Multiplayer::on_board(
+            Variant::Connect6,
+            Board::with_size(BoardSize::CONNECT6),
+            2,
+            &Player::X,
+        )
+        .with_schedule(TurnSchedule::CONNECT6);
+        connect6.play(&Player::X, (0, 0)).unwrap();
+
+        let stones = take_turn(&mut connect6, &Player::O).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(stones, vec![(9, 9), (10, 10)]);
+        assert_eq!(
+            connect6.board.get_cell_at(9, 9),
+            Ok(&Cell::Marker(Player::O))
+        );
+        assert_eq!(
+            connect6.board.get_cell_at(10, 10),
+            Ok(&Cell::Marker(Player::O))
+        );
+        assert_eq!(connect6.current_player(), Player::X);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::cell::Cell;
+use crate::models::custom::{Custom, Position};
+use crate::models::definition::VariantDefinition;
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove};
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use crate::utils::definitions::find_definition;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_custom_rules;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_losing_player;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_line;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_board_position;
+use crate::utils::input::ask_for_column;
+use crate::utils::rules_ai::best_rules_move;
+
+// Roughly how many positions the computer looks at before it stops deepening.
+const SEARCH_BUDGET: usize = 200_000;
+
+// How far ahead the search can afford to look. Without gravity every mark
+// takes one of the open cells away; with it the columns mostly stay open.
+fn search_depth(custom: &Custom) -> usize {
+    let move_count = custom.legal_moves().len();
+    let empty_cells = custom
+        .board
+        .get_cell_positions()
+        .iter()
+        .filter(|(cell, _, _)| **cell == Cell::Empty)
+        .count();
+    let mut positions: usize = 1;
+    let mut depth = 0;
+
+    while depth < empty_cells {
+        let branching = if custom.definition.gravity {
+            move_count
+        } else {
+            move_count - depth
+        };
+        positions = positions.saturating_mul(branching);
+        if positions > SEARCH_BUDGET {
+            break;
+        }
+        depth += 1;
+    }
+
+    depth.max(1)
+}
+
+impl Playable for Custom<'_> {
+    fn render(&self) {
+        render_board(&self.board);
+        render_empty_lines(1);
+        render_custom_rules(self);
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match &outcome.result {
+            GameResult::Winner(winner) if self.definition.misere => {
+                render_losing_player(&winner.opponent())
+            }
+            GameResult::Winner(winner) => render_winning_player(winner),
+            GameResult::Draw | GameResult::Unfinished => render_stalemate(),
+        }
+        if let Some((_, line)) = self.completed_line() {
+            render_winning_line(&line);
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        let size = self.board.size();
+        if self.definition.gravity {
+            let prompt = format!("Which column? (1-{})", size.cols);
+            let col_index = ask_for_column(&prompt, size.cols)?;
+            return Ok(self
+                .landing_cell(col_index)
+                .ok_or(MoveError::ColumnFull(col_index))?);
+        }
+
+        ask_for_board_position("Which cell? (e.g., A1, C2, etc.)", size)
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<Position> {
+        best_rules_move(self, search_depth(self))
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(
+    seating: &Seating,
+    definition: &VariantDefinition,
+    options: &GameOptions,
+) -> Result<(), Error> {
+    let mut custom = Custom::new(definition, &seating.first_player);
+    let mut record = new_record(Variant::Custom, seating);
+    record.metadata.definition = Some(definition.name.clone());
+    record.metadata.board_size = Some(custom.board.size());
+    if custom.schedule() != TurnSchedule::ALTERNATING {
+        record.metadata.schedule = Some(custom.schedule());
+    }
+
+    next_turn(&mut custom, seating, &mut record, options)
+}
+
+// The rules come from the variant file the record names, so that file has to
+// still be around.
+pub fn replay(record: &GameRecord, definitions: &[VariantDefinition]) {
+    let metadata = &record.metadata;
+    let name = metadata.definition.as_deref().unwrap_or_default();
+
+    match find_definition(definitions, name) {
+        Some(definition) => {
+            replay_game(&mut Custom::new(definition, &metadata.first_player), record)
+        }
+        None => println!("There's no variant file for '{}' to replay it with", name),
+    }
+}
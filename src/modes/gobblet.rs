@@ -0,0 +1,70 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove, Termination};
+use crate::models::gobblet::{Gobblet, GobbletMove};
+use crate::models::player::Player;
+use crate::models::rules::Outcome;
+use crate::models::variant::Variant;
+use crate::utils::gobblet_ai::SEARCH_DEPTH;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_repetition;
+use crate::utils::graphics::render_reserves;
+use crate::utils::graphics::render_stacks;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_gobblet_move;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for Gobblet {
+    fn render(&self) {
+        render_reserves(self);
+        render_empty_lines(1);
+        render_stacks(self);
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match (&outcome.result, &outcome.termination) {
+            (_, Termination::Repetition) => render_repetition(),
+            (GameResult::Winner(winner), _) => render_winning_player(winner),
+            (GameResult::Draw | GameResult::Unfinished, _) => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<GobbletMove, Error> {
+        ask_for_gobblet_move("Which size and cell, or which piece to move? (e.g., L B2, A1-C3)")
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<GobbletMove> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, gobblet_move: GobbletMove) {
+        match gobblet_move {
+            GobbletMove::Place { size, to } => record.push_sized_move(player, size, to),
+            GobbletMove::Move { from, to } => record.push_slide(player, from, to),
+        }
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<GobbletMove, MoveError> {
+        let to = (recorded.row_index, recorded.col_index);
+        match (recorded.from, recorded.size) {
+            (Some(from), _) => Ok(GobbletMove::Move { from, to }),
+            (None, Some(size)) => Ok(GobbletMove::Place { size, to }),
+            (None, None) => Err(MoveError::Unrecorded {
+                position: to,
+                missing: "size",
+            }),
+        }
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut gobblet = Gobblet::new(&seating.first_player);
+    let mut record = new_record(Variant::Gobblet, seating);
+
+    next_turn(&mut gobblet, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Gobblet::new(&record.metadata.first_player), record);
+}
@@ -0,0 +1,217 @@
+use crate::error::{Error, MoveError};
+use crate::game::{
+    new_record, next_turn, replay_game, save_record, GameOptions, Playable, Seating,
+};
+use crate::models::board::BoardSize;
+use crate::models::game_record::{GameMetadata, GameRecord, RecordedMove};
+use crate::models::gomoku::{Gomoku, GomokuRule, Opening, Position, SwapChoice, BLACK, WHITE};
+use crate::models::player::Player;
+use crate::models::rules::Rules;
+use crate::models::variant::Variant;
+use crate::utils::gomoku_ai::{balanced_opening_stone, best_gomoku_move, preferred_colour};
+use crate::utils::graphics::clear_screen;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_gomoku_rules;
+use crate::utils::graphics::render_seats;
+use crate::utils::input::ask_for_board_position;
+use crate::utils::input::ask_for_swap_choice;
+use crate::utils::input::with_retries;
+use std::mem;
+
+impl Playable for Gomoku {
+    fn render_heading(&self, metadata: &GameMetadata) {
+        render_gomoku_rules(self.rule, metadata.opening);
+        render_seats(metadata);
+    }
+
+    fn render(&self) {
+        render_board(&self.board);
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        ask_for_board_position("Which cell? (e.g., H8)", self.board.size())
+    }
+
+    fn computer_move(&self, player: &Player) -> Option<Position> {
+        best_gomoku_move(self, player)
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+// Black always moves first, so whoever takes the first move takes X, at
+// least until a swap opening hands the colours round.
+pub fn play(
+    seating: &Seating,
+    size: BoardSize,
+    rule: GomokuRule,
+    opening: Opening,
+    options: &GameOptions,
+) -> Result<(), Error> {
+    let mut seating = Seating {
+        player_character: if seating.first_player == BLACK {
+            seating.player_character.clone()
+        } else {
+            seating.player_character.opponent()
+        },
+        first_player: BLACK,
+        against_computer: seating.against_computer,
+    };
+    let mut gomoku = Gomoku::new(size, rule);
+    let mut record = new_record(Variant::Gomoku, &seating);
+    record.metadata.board_size = Some(size);
+    record.metadata.rule = Some(rule);
+    record.metadata.opening = Some(opening);
+
+    if let Err(error) = open_game(&mut gomoku, &mut seating, opening, &mut record) {
+        save_record(&record, options);
+        return Err(error);
+    }
+
+    next_turn(&mut gomoku, &seating, &mut record, options)
+}
+
+// Runs the swap part of the game. Until someone picks a colour, the seat
+// that started holds Black and lays the opening stones.
+fn open_game(
+    gomoku: &mut Gomoku,
+    seating: &mut Seating,
+    opening: Opening,
+    record: &mut GameRecord,
+) -> Result<(), Error> {
+    if opening == Opening::Plain {
+        return Ok(());
+    }
+
+    place_opening_stones(gomoku, seating, &BLACK, 3, record)?;
+    let (chooser, choice) =
+        match ask_swap(gomoku, seating, &WHITE, opening == Opening::Swap2, record)? {
+            SwapChoice::AddTwo => {
+                place_opening_stones(gomoku, seating, &WHITE, 2, record)?;
+                (BLACK, ask_swap(gomoku, seating, &BLACK, false, record)?)
+            }
+            choice => (WHITE, choice),
+        };
+
+    if choice != SwapChoice::Take(chooser) {
+        seating.player_character = seating.player_character.opponent();
+        mem::swap(&mut record.metadata.x, &mut record.metadata.o);
+    }
+
+    Ok(())
+}
+
+fn render_opening(gomoku: &Gomoku, record: &GameRecord, message: &str) -> Result<(), Error> {
+    clear_screen()?;
+    render_empty_lines(1);
+    render_gomoku_rules(gomoku.rule, record.metadata.opening);
+    render_seats(&record.metadata);
+    println!("{}", message);
+    render_empty_lines(1);
+    render_board(&gomoku.board);
+    render_empty_lines(1);
+    Ok(())
+}
+
+// The seat is named by the colour it holds for now; the stones go down in
+// whatever colour comes next.
+fn place_opening_stones(
+    gomoku: &mut Gomoku,
+    seating: &Seating,
+    seat: &Player,
+    count: usize,
+    record: &mut GameRecord,
+) -> Result<(), Error> {
+    for number in 1..=count {
+        let stone = gomoku.current_player();
+        render_opening(
+            gomoku,
+            record,
+            &format!(
+                "Opening: {} lays stone {} of {} ({})",
+                seat.character(),
+                number,
+                count,
+                colour_name(&stone)
+            ),
+        )?;
+
+        let position = if seating.is_computer(seat) {
+            let position = balanced_opening_stone(gomoku)
+                .ok_or_else(|| Error::NoMoveAvailable(seat.clone()))?;
+            gomoku.play(&stone, position)?;
+            position
+        } else {
+            let size = gomoku.board.size();
+            with_retries(|| {
+                let position = ask_for_board_position("Which cell? (e.g., H8)", size)?;
+                gomoku.play(&stone, position)?;
+                Ok(position)
+            })?
+        };
+        record.push_move(&stone, position.0, position.1);
+    }
+
+    Ok(())
+}
+
+// The computer takes whichever colour it rates higher and never asks for
+// more stones.
+fn ask_swap(
+    gomoku: &Gomoku,
+    seating: &Seating,
+    seat: &Player,
+    can_add_two: bool,
+    record: &GameRecord,
+) -> Result<SwapChoice, Error> {
+    render_opening(
+        gomoku,
+        record,
+        &format!("Opening: {} picks a colour", seat.character()),
+    )?;
+
+    if seating.is_computer(seat) {
+        let colour = preferred_colour(gomoku);
+        println!("{} takes {}.", seat.character(), colour_name(&colour));
+        return Ok(SwapChoice::Take(colour));
+    }
+
+    let prompt = if can_add_two {
+        "Play black or white, or add two more stones? (b/w/m)"
+    } else {
+        "Play black or white? (b/w)"
+    };
+    ask_for_swap_choice(prompt, can_add_two)
+}
+
+// Colour choices aren't moves, so only the stones are replayed; the record's
+// seats already say who ended up with which colour.
+pub fn replay(record: &GameRecord) {
+    let metadata = &record.metadata;
+    let rule = metadata.rule.unwrap_or_default();
+    render_gomoku_rules(rule, metadata.opening);
+    replay_game(
+        &mut Gomoku::new(metadata.board_size.unwrap_or(BoardSize::GOMOKU), rule),
+        record,
+    );
+}
+
+fn colour_name(player: &Player) -> &str {
+    if player == &BLACK {
+        "black"
+    } else {
+        "white"
+    }
+}
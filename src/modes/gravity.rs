@@ -0,0 +1,62 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::board::BoardSize;
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::gravity::{Gravity, Position};
+use crate::models::player::Player;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_board_with_drop;
+use crate::utils::gravity_ai::SEARCH_DEPTH;
+use crate::utils::input::ask_for_column;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for Gravity {
+    fn render(&self) {
+        render_board_with_drop(&self.board, self.last_drop);
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        let col_count = self.board.size().cols;
+        let prompt = format!("Which column? (1-{})", col_count);
+        let col_index = ask_for_column(&prompt, col_count)?;
+        Ok(self
+            .landing_cell(col_index)
+            .ok_or(MoveError::ColumnFull(col_index))?)
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<Position> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, size: BoardSize, options: &GameOptions) -> Result<(), Error> {
+    let mut gravity = Gravity::new(size, &seating.first_player);
+    let mut record = new_record(Variant::Gravity, seating);
+    record.metadata.board_size = Some(size);
+
+    next_turn(&mut gravity, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    let size = record
+        .metadata
+        .board_size
+        .unwrap_or_else(|| Variant::Gravity.default_board_size());
+    replay_game(
+        &mut Gravity::new(size, &record.metadata.first_player),
+        record,
+    );
+}
@@ -0,0 +1,74 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove, Termination};
+use crate::models::infinite::{Infinite, MOVE_LIMIT};
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_board_with_fading;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_move_limit;
+use crate::utils::graphics::render_repetition;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::infinite_ai::SEARCH_DEPTH;
+use crate::utils::input::ask_for_cell_position;
+use crate::utils::rules_ai::best_rules_move;
+
+type Position = (usize, usize);
+
+impl Playable for Infinite {
+    fn render(&self) {
+        let fading_marks: Vec<Position> = [Player::X, Player::O]
+            .iter()
+            .filter_map(|player| self.fading_mark(player))
+            .collect();
+        render_board_with_fading(&self.board, &fading_marks);
+
+        if self.outcome().is_none() && self.fading_mark(&self.current_player()).is_some() {
+            render_empty_lines(1);
+            println!("Lowercase marks vanish when their owner plays next.");
+        }
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match (&outcome.result, &outcome.termination) {
+            (_, Termination::Repetition) => render_repetition(),
+            (_, Termination::MoveLimit) => render_move_limit(MOVE_LIMIT),
+            (GameResult::Winner(winner), _) => render_winning_player(winner),
+            (GameResult::Draw | GameResult::Unfinished, _) => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        ask_for_cell_position("Which cell? (e.g., A1, C2, etc.)")
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<Position> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut infinite = Infinite::new(&seating.first_player);
+    let mut record = new_record(Variant::Infinite, seating);
+
+    next_turn(&mut infinite, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Infinite::new(&record.metadata.first_player), record);
+}
@@ -0,0 +1,17 @@
+pub mod classic;
+pub mod connect6;
+pub mod custom;
+pub mod gobblet;
+pub mod gomoku;
+pub mod gravity;
+pub mod infinite;
+pub mod morris;
+pub mod multiplayer;
+pub mod notakto;
+pub mod numerical;
+pub mod order_chaos;
+pub mod phantom;
+pub mod quantum;
+pub mod qubic;
+pub mod ultimate;
+pub mod wild;
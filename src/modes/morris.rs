@@ -0,0 +1,72 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove, Termination};
+use crate::models::morris::{Morris, MorrisMove};
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_repetition;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_morris_move;
+use crate::utils::rules_ai::best_rules_move;
+
+// With so few pieces the tree stays small enough to look well ahead. There's
+// nothing to judge a position by short of a line, so anything the search
+// can't finish scores as even.
+const SEARCH_DEPTH: usize = 8;
+
+impl Playable for Morris {
+    fn render(&self) {
+        render_board(&self.board);
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match (&outcome.result, &outcome.termination) {
+            (_, Termination::Repetition) => render_repetition(),
+            (GameResult::Winner(winner), _) => render_winning_player(winner),
+            (GameResult::Draw | GameResult::Unfinished, _) => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<MorrisMove, Error> {
+        if self.is_placing(&self.current_player()) {
+            ask_for_morris_move("Where to place a piece? (e.g., A1, C2, etc.)")
+        } else {
+            ask_for_morris_move("Which piece to move, and where? (e.g., A1-B1)")
+        }
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<MorrisMove> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, morris_move: MorrisMove) {
+        match morris_move {
+            MorrisMove::Place((row_index, col_index)) => {
+                record.push_move(player, row_index, col_index)
+            }
+            MorrisMove::Slide { from, to } => record.push_slide(player, from, to),
+        }
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<MorrisMove, MoveError> {
+        let to = (recorded.row_index, recorded.col_index);
+        Ok(match recorded.from {
+            Some(from) => MorrisMove::Slide { from, to },
+            None => MorrisMove::Place(to),
+        })
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut morris = Morris::new(&seating.first_player);
+    let mut record = new_record(Variant::Morris, seating);
+
+    next_turn(&mut morris, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Morris::new(&record.metadata.first_player), record);
+}
@@ -0,0 +1,132 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::board::Board;
+use crate::models::game_record::{GameMetadata, GameRecord, GameResult, RecordedMove};
+use crate::models::multiplayer::{Multiplayer, Position};
+use crate::models::player::Player;
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::ai::winning_line_on;
+use crate::utils::connect6_ai::best_connect6_stone;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_seats;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_line;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_board_position;
+use crate::utils::input::ask_for_board_positions;
+use crate::utils::multiplayer_ai::best_multiplayer_move;
+
+impl Playable for Multiplayer {
+    fn render_heading(&self, metadata: &GameMetadata) {
+        render_seats(metadata);
+    }
+
+    fn render(&self) {
+        render_board(&self.board);
+        if self.outcome().is_none() && self.marks_left_in_turn() > 1 {
+            render_empty_lines(1);
+            println!("{} marks to place this turn", self.marks_left_in_turn());
+        }
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match &outcome.result {
+            GameResult::Winner(winner) => render_winning_player(winner),
+            GameResult::Draw | GameResult::Unfinished => render_stalemate(),
+        }
+        if let Some(line) = winning_line_on(&self.board) {
+            render_winning_line(&line);
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        let prompt = match self.variant() {
+            Variant::Connect6 => "Which cell? (e.g., J10)",
+            _ => "Which cell? (e.g., A1, C2, etc.)",
+        };
+        ask_for_board_position(prompt, self.board.size())
+    }
+
+    fn ask_for_moves(&self) -> Result<Vec<Position>, Error> {
+        match self.marks_left_in_turn() {
+            1 => Ok(vec![self.ask_for_move()?]),
+            count => ask_for_board_positions(
+                &format!("Which {} cells? (e.g., J10 K11)", count),
+                self.board.size(),
+                count,
+            ),
+        }
+    }
+
+    fn computer_move(&self, player: &Player) -> Option<Position> {
+        match self.variant() {
+            Variant::Connect6 => best_connect6_stone(self, player),
+            _ => best_multiplayer_move(self, player),
+        }
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+// Plays k in a row on any board with any number of seats. Shaped games are
+// the two-player case, usually on a board with holes in it.
+pub fn play(
+    seating: &Seating,
+    variant: Variant,
+    board: Board,
+    player_count: usize,
+    options: &GameOptions,
+) -> Result<(), Error> {
+    let mut record = new_record(variant, seating);
+    record.metadata.board_size = Some(board.size());
+    if board.has_blocked_cells() {
+        record.metadata.shape = Some(board.mask());
+    }
+    record.metadata.toroidal = board.toroidal;
+    record.metadata.others = Player::seats(player_count)[2..]
+        .iter()
+        .map(|player| seating.participant(player, variant))
+        .collect();
+    let mut multiplayer =
+        Multiplayer::on_board(variant, board, player_count, &seating.first_player);
+
+    next_turn(&mut multiplayer, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    let metadata = &record.metadata;
+    let size = metadata
+        .board_size
+        .unwrap_or_else(|| metadata.variant.default_board_size());
+    let mut board = metadata
+        .shape
+        .as_ref()
+        .and_then(|shape| Board::from_mask(shape, size.win_length))
+        .unwrap_or_else(|| Board::with_size(size));
+    board.toroidal = metadata.toroidal;
+
+    render_seats(metadata);
+    replay_game(
+        &mut Multiplayer::on_board(
+            metadata.variant,
+            board,
+            metadata.player_count(),
+            &metadata.first_player,
+        )
+        .with_schedule(metadata.schedule.unwrap_or_default()),
+        record,
+    );
+}
@@ -0,0 +1,62 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove};
+use crate::models::notakto::{Notakto, NotaktoMove, DEFAULT_BOARD_COUNT};
+use crate::models::player::Player;
+use crate::models::rules::Outcome;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_losing_player;
+use crate::utils::graphics::render_notakto;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::input::ask_for_notakto_move;
+use crate::utils::notakto_ai::best_notakto_move;
+
+impl Playable for Notakto {
+    fn render(&self) {
+        render_notakto(self);
+    }
+
+    // Whoever killed the last board is the one to blame.
+    fn render_outcome(&self, outcome: &Outcome) {
+        match &outcome.result {
+            GameResult::Winner(winner) => render_losing_player(&winner.opponent()),
+            GameResult::Draw | GameResult::Unfinished => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<NotaktoMove, Error> {
+        ask_for_notakto_move("Which board and cell? (e.g., 2 B3)", self.boards.len())
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<NotaktoMove> {
+        best_notakto_move(self)
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, notakto_move: NotaktoMove) {
+        record.push_cube_move(player, notakto_move);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<NotaktoMove, MoveError> {
+        let board_index = recorded.layer_index.ok_or(MoveError::Unrecorded {
+            position: (recorded.row_index, recorded.col_index),
+            missing: "board",
+        })?;
+        Ok((board_index, recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, board_count: usize, options: &GameOptions) -> Result<(), Error> {
+    let mut notakto = Notakto::new(board_count, &seating.first_player);
+    let mut record = new_record(Variant::Notakto, seating);
+    record.metadata.board_count = Some(board_count);
+
+    next_turn(&mut notakto, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    let board_count = record.metadata.board_count.unwrap_or(DEFAULT_BOARD_COUNT);
+    replay_game(
+        &mut Notakto::new(board_count, &record.metadata.first_player),
+        record,
+    );
+}
@@ -0,0 +1,66 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, GameResult, RecordedMove};
+use crate::models::numerical::{NumberedMove, Numerical};
+use crate::models::player::Player;
+use crate::models::rules::Outcome;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_numbers_left;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_line;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_numbered_move;
+use crate::utils::numerical_ai::SEARCH_DEPTH;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for Numerical {
+    fn render(&self) {
+        render_numbers_left(self);
+        render_empty_lines(1);
+        render_board(&self.board);
+    }
+
+    fn render_outcome(&self, outcome: &Outcome) {
+        match &outcome.result {
+            GameResult::Winner(winner) => render_winning_player(winner),
+            GameResult::Draw | GameResult::Unfinished => render_stalemate(),
+        }
+        if let Some(line) = self.winning_line() {
+            render_winning_line(&line);
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<NumberedMove, Error> {
+        ask_for_numbered_move("Which number and cell? (e.g., 5 B2, 8 A3)")
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<NumberedMove> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, numbered_move: NumberedMove) {
+        record.push_numbered_move(player, numbered_move);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<NumberedMove, MoveError> {
+        let number = recorded.number.ok_or(MoveError::Unrecorded {
+            position: (recorded.row_index, recorded.col_index),
+            missing: "number",
+        })?;
+        Ok((number, recorded.row_index, recorded.col_index))
+    }
+}
+
+// Whoever moves first takes the odd numbers, which gives them the extra one.
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut numerical = Numerical::new(&seating.first_player);
+    let mut record = new_record(Variant::Numerical, seating);
+
+    next_turn(&mut numerical, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Numerical::new(&record.metadata.first_player), record);
+}
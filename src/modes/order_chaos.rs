@@ -0,0 +1,72 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameMetadata, GameRecord, RecordedMove};
+use crate::models::order_chaos::OrderChaos;
+use crate::models::player::Player;
+use crate::models::rules::Outcome;
+use crate::models::variant::Variant;
+use crate::models::wild::WildMove;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_roles;
+use crate::utils::graphics::render_winning_role;
+use crate::utils::input::ask_for_wild_move;
+use crate::utils::order_chaos_ai::SEARCH_DEPTH;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for OrderChaos {
+    fn render_heading(&self, _metadata: &GameMetadata) {
+        render_roles(&self.order);
+    }
+
+    fn render(&self) {
+        render_board(&self.board);
+    }
+
+    fn render_outcome(&self, _outcome: &Outcome) {
+        if let Some(role) = self.winning_role() {
+            render_winning_role(role, &self.player_for(role));
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<WildMove, Error> {
+        ask_for_wild_move(
+            "Which symbol and cell? (e.g., X C3, O F6)",
+            self.board.size(),
+        )
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<WildMove> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (symbol, row_index, col_index): WildMove,
+    ) {
+        record.push_wild_move(player, symbol, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<WildMove, MoveError> {
+        let symbol = recorded.symbol.ok_or(MoveError::Unrecorded {
+            position: (recorded.row_index, recorded.col_index),
+            missing: "symbol",
+        })?;
+        Ok((symbol, recorded.row_index, recorded.col_index))
+    }
+}
+
+// Order always moves first, so whoever takes the first move takes Order.
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut order_chaos = OrderChaos::new(&seating.first_player);
+    let mut record = new_record(Variant::OrderChaos, seating);
+
+    next_turn(&mut order_chaos, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    let mut order_chaos = OrderChaos::new(&record.metadata.first_player);
+    render_roles(&order_chaos.order);
+    replay_game(&mut order_chaos, record);
+}
@@ -0,0 +1,103 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::phantom::{Phantom, Position, Viewer};
+use crate::models::player::Player;
+use crate::models::rules::Rules;
+use crate::models::variant::Variant;
+use crate::utils::graphics::clear_screen;
+use crate::utils::graphics::render_board;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_found_mark;
+use crate::utils::graphics::render_marks_found_by;
+use crate::utils::input::ask_for_cell_position;
+use crate::utils::input::ask_for_string;
+use crate::utils::phantom_ai::best_phantom_probe;
+
+impl Playable for Phantom {
+    // Nothing is hidden once it's over.
+    fn render(&self) {
+        if self.outcome().is_some() {
+            return render_board(&self.board);
+        }
+
+        let player = self.current_player();
+        let viewer = match &self.viewer {
+            Viewer::Everyone => return render_board(&self.board),
+            Viewer::Turn => &player,
+            Viewer::Seat(seat) => seat,
+        };
+
+        println!("You only see your own marks and the ones you've found.");
+        render_marks_found_by(&viewer.opponent(), self.found_by(&viewer.opponent()));
+        render_empty_lines(1);
+        render_board(&self.view(viewer));
+
+        if let Some(position) = self.just_found().filter(|_| viewer == &player) {
+            render_empty_lines(1);
+            render_found_mark(position);
+        }
+    }
+
+    // The screen is cleared and handed over before each turn so neither
+    // player sees the other's marks. A player who just found one keeps the
+    // keyboard.
+    fn hand_over(&self, player: &Player) -> Result<(), Error> {
+        if self.just_found().is_some() {
+            return Ok(());
+        }
+
+        clear_screen()?;
+        render_empty_lines(1);
+        ask_for_string(&format!(
+            "Pass the keyboard to player {} and press Enter.",
+            player.character()
+        ))?;
+        Ok(())
+    }
+
+    fn ask_for_move(&self) -> Result<Position, Error> {
+        ask_for_cell_position("Which cell? (e.g., A1, C2, etc.)")
+    }
+
+    // The computer is told about the marks it finds just as a human would be.
+    fn computer_move(&self, player: &Player) -> Option<Position> {
+        best_phantom_probe(self, player)
+    }
+
+    // Only the marks that went down are recorded, so a replay shows the full
+    // board rather than what either player saw.
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (row_index, col_index): Position,
+    ) {
+        if self.just_found().is_none() {
+            record.push_move(player, row_index, col_index);
+        }
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<Position, MoveError> {
+        Ok((recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let viewer = if seating.against_computer {
+        Viewer::Seat(seating.player_character.clone())
+    } else {
+        Viewer::Turn
+    };
+    let mut phantom = Phantom::new(&seating.first_player, viewer);
+    let mut record = new_record(Variant::Phantom, seating);
+
+    next_turn(&mut phantom, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(
+        &mut Phantom::new(&record.metadata.first_player, Viewer::Everyone),
+        record,
+    );
+}
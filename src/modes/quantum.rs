@@ -0,0 +1,108 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::player::Player;
+use crate::models::quantum::{Quantum, QuantumMove};
+use crate::models::rules::{Outcome, Rules};
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_quantum;
+use crate::utils::graphics::render_split_win;
+use crate::utils::graphics::render_stalemate;
+use crate::utils::graphics::render_winning_player;
+use crate::utils::input::ask_for_cell_position;
+use crate::utils::input::ask_for_spooky_move;
+use crate::utils::notation::cell_name;
+use crate::utils::quantum_ai::{best_collapse, best_quantum_move};
+
+impl Playable for Quantum {
+    fn render(&self) {
+        render_quantum(self);
+
+        if let Some(mark_index) = self.pending_collapse {
+            let mark = &self.marks[mark_index];
+            render_empty_lines(1);
+            println!(
+                "{} closed a cycle. Player {}, where does it land: {} or {}?",
+                mark.label(),
+                self.current_player().character(),
+                cell_name(mark.cells.0 .0, mark.cells.0 .1),
+                cell_name(mark.cells.1 .0, mark.cells.1 .1)
+            );
+        } else if self.outcome().is_none() && self.needs_classical_move() {
+            render_empty_lines(1);
+            println!("Only one cell is left, so this mark goes down classically.");
+        }
+    }
+
+    fn render_outcome(&self, _outcome: &Outcome) {
+        match self.quantum_outcome() {
+            Some(outcome) => match (outcome.winner, outcome.runner_up) {
+                (Some(winner), Some(runner_up)) => render_split_win(&winner, &runner_up),
+                (Some(winner), None) => render_winning_player(&winner),
+                (None, _) => render_stalemate(),
+            },
+            None => render_stalemate(),
+        }
+    }
+
+    fn ask_for_move(&self) -> Result<QuantumMove, Error> {
+        if self.pending_collapse.is_some() {
+            return ask_for_cell_position("Which cell?").map(QuantumMove::Collapse);
+        }
+
+        if self.needs_classical_move() {
+            return ask_for_cell_position("Which cell? (e.g., A1, C2, etc.)")
+                .map(QuantumMove::Classical);
+        }
+
+        let (first, second) = ask_for_spooky_move("Which two cells? (e.g., A1 B2)")?;
+        Ok(QuantumMove::Spooky(first, second))
+    }
+
+    fn computer_move(&self, player: &Player) -> Option<QuantumMove> {
+        if self.pending_collapse.is_some() {
+            return best_collapse(self, player).map(|(landed, _)| QuantumMove::Collapse(landed));
+        }
+        best_quantum_move(self, player)
+    }
+
+    // A collapse goes on the move that closed the cycle.
+    fn record_move(&self, record: &mut GameRecord, player: &Player, quantum_move: QuantumMove) {
+        match quantum_move {
+            QuantumMove::Spooky(first, second) => record.push_spooky_move(player, (first, second)),
+            QuantumMove::Classical((row_index, col_index)) => {
+                record.push_move(player, row_index, col_index)
+            }
+            QuantumMove::Collapse(landed) => record.record_collapse(landed),
+        }
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<QuantumMove, MoveError> {
+        let position = (recorded.row_index, recorded.col_index);
+        Ok(match recorded.pair {
+            Some(second) => QuantumMove::Spooky(position, second),
+            None => QuantumMove::Classical(position),
+        })
+    }
+
+    // The other player chose where a cycle-closing mark landed.
+    fn recorded_moves(recorded: &RecordedMove) -> Result<Vec<(Player, QuantumMove)>, MoveError> {
+        let mut moves = vec![(recorded.player.clone(), Self::recorded_move(recorded)?)];
+        if let Some(landed) = recorded.collapse {
+            moves.push((recorded.player.opponent(), QuantumMove::Collapse(landed)));
+        }
+        Ok(moves)
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut quantum = Quantum::new(&seating.first_player);
+    let mut record = new_record(Variant::Quantum, seating);
+
+    next_turn(&mut quantum, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Quantum::new(&record.metadata.first_player), record);
+}
@@ -0,0 +1,50 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::cube::{Cube, CubePosition, CUBE_SIZE};
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::player::Player;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_cube;
+use crate::utils::input::ask_for_cube_position;
+use crate::utils::qubic_ai::SEARCH_DEPTH;
+use crate::utils::rules_ai::best_rules_move;
+
+impl Playable for Cube {
+    fn render(&self) {
+        render_cube(self);
+    }
+
+    fn ask_for_move(&self) -> Result<CubePosition, Error> {
+        ask_for_cube_position("Which cell? (layer, row, column, e.g., 2 B3)", self.size)
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<CubePosition> {
+        best_rules_move(self, SEARCH_DEPTH)
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, position: CubePosition) {
+        record.push_cube_move(player, position);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<CubePosition, MoveError> {
+        let layer_index = recorded.layer_index.ok_or(MoveError::Unrecorded {
+            position: (recorded.row_index, recorded.col_index),
+            missing: "layer",
+        })?;
+        Ok((layer_index, recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut cube = Cube::new(CUBE_SIZE, &seating.first_player);
+    let mut record = new_record(Variant::Qubic, seating);
+
+    next_turn(&mut cube, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(
+        &mut Cube::new(CUBE_SIZE, &record.metadata.first_player),
+        record,
+    );
+}
@@ -0,0 +1,58 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::meta_board::{from_global, to_global, MetaBoard, MetaMove};
+use crate::models::player::Player;
+use crate::models::rules::Rules;
+use crate::models::variant::Variant;
+use crate::utils::graphics::render_available_boards;
+use crate::utils::graphics::render_empty_lines;
+use crate::utils::graphics::render_meta_board;
+use crate::utils::input::ask_for_meta_move;
+use crate::utils::rules_ai::best_rules_move;
+use crate::utils::ultimate_ai::search_depth;
+
+impl Playable for MetaBoard {
+    fn render(&self) {
+        render_meta_board(self);
+        if self.outcome().is_none() {
+            render_empty_lines(1);
+            render_available_boards(self);
+        }
+    }
+
+    // When only one board is open there's no need to name it.
+    fn ask_for_move(&self) -> Result<MetaMove, Error> {
+        match self.available_boards().as_slice() {
+            [board_position] => ask_for_meta_move(
+                "Which cell? (e.g., A1, or B2 A1 for board and cell)",
+                Some(*board_position),
+            ),
+            _ => ask_for_meta_move("Which board and cell? (e.g., B2 A1)", None),
+        }
+    }
+
+    fn computer_move(&self, _player: &Player) -> Option<MetaMove> {
+        best_rules_move(self, search_depth(self))
+    }
+
+    fn record_move(&self, record: &mut GameRecord, player: &Player, meta_move: MetaMove) {
+        let (row_index, col_index) = to_global(meta_move);
+        record.push_move(player, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<MetaMove, MoveError> {
+        Ok(from_global(recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut meta_board = MetaBoard::new(&seating.first_player);
+    let mut record = new_record(Variant::Ultimate, seating);
+
+    next_turn(&mut meta_board, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut MetaBoard::new(&record.metadata.first_player), record);
+}
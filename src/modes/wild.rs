@@ -0,0 +1,55 @@
+use crate::error::{Error, MoveError};
+use crate::game::{new_record, next_turn, replay_game, GameOptions, Playable, Seating};
+use crate::models::game_record::{GameRecord, RecordedMove};
+use crate::models::player::Player;
+use crate::models::variant::Variant;
+use crate::models::wild::{Wild, WildMove};
+use crate::utils::graphics::render_board;
+use crate::utils::input::ask_for_wild_move;
+use crate::utils::wild_ai::best_wild_move;
+
+impl Playable for Wild {
+    fn render(&self) {
+        render_board(&self.board);
+    }
+
+    fn ask_for_move(&self) -> Result<WildMove, Error> {
+        ask_for_wild_move(
+            "Which symbol and cell? (e.g., X B2, O A1)",
+            self.board.size(),
+        )
+    }
+
+    // The whole game is small enough to solve, so there's no depth to pick.
+    fn computer_move(&self, _player: &Player) -> Option<WildMove> {
+        best_wild_move(&self.board)
+    }
+
+    fn record_move(
+        &self,
+        record: &mut GameRecord,
+        player: &Player,
+        (symbol, row_index, col_index): WildMove,
+    ) {
+        record.push_wild_move(player, symbol, row_index, col_index);
+    }
+
+    fn recorded_move(recorded: &RecordedMove) -> Result<WildMove, MoveError> {
+        let symbol = recorded.symbol.ok_or(MoveError::Unrecorded {
+            position: (recorded.row_index, recorded.col_index),
+            missing: "symbol",
+        })?;
+        Ok((symbol, recorded.row_index, recorded.col_index))
+    }
+}
+
+pub fn play(seating: &Seating, options: &GameOptions) -> Result<(), Error> {
+    let mut wild = Wild::new(&seating.first_player);
+    let mut record = new_record(Variant::Wild, seating);
+
+    next_turn(&mut wild, seating, &mut record, options)
+}
+
+pub fn replay(record: &GameRecord) {
+    replay_game(&mut Wild::new(&record.metadata.first_player), record);
+}
@@ -1,33 +1,26 @@
 use crate::models::board::Board;
 use crate::models::cell::Cell;
 use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use std::cmp::Reverse;
 
 pub fn all_are_player(cells: &[&Cell], player: &Player) -> bool {
     cells.iter().all(|cell| match cell {
         Cell::Marker(p) => p == player,
-        Cell::Empty => false,
+        Cell::Empty | Cell::Blocked | Cell::Number(_) | Cell::Symbol(_) => false,
     })
 }
 
 pub fn same_player_in(cells: &[&Cell]) -> Option<Player> {
-    if all_are_player(cells, &Player::X) {
-        Some(Player::X)
-    } else if all_are_player(cells, &Player::O) {
-        Some(Player::O)
-    } else {
-        None
-    }
+    Player::ALL
+        .iter()
+        .find(|player| all_are_player(cells, player))
+        .cloned()
 }
 
 pub fn win_arrangements(board: &Board) -> Vec<Vec<(&Cell, usize, usize)>> {
-    vec![
-        board.get_cell_position_rows(),
-        board.get_cell_position_cols(),
-        board.get_cell_position_crosses(),
-    ]
-    .iter()
-    .flat_map(|cell_position_collections| cell_position_collections.to_vec())
-    .collect()
+    board.get_cell_position_lines()
 }
 
 pub fn winning_player_on(board: &Board) -> Option<Player> {
@@ -37,9 +30,76 @@ pub fn winning_player_on(board: &Board) -> Option<Player> {
     })
 }
 
+// The symbol filling a completed line, for games where the marks don't
+// belong to anyone.
+pub fn winning_symbol_on(board: &Board) -> Option<Symbol> {
+    win_arrangements(board).iter().find_map(|cell_positions| {
+        let cells = Board::cell_positions_to_cells(cell_positions);
+        Symbol::ALL
+            .iter()
+            .find(|symbol| cells.iter().all(|cell| **cell == Cell::Symbol(**symbol)))
+            .copied()
+    })
+}
+
+// The cells of the first completed line, in order along the line.
+pub fn winning_line_on(board: &Board) -> Option<Vec<(usize, usize)>> {
+    line_where(board, |cells| same_player_in(cells).is_some())
+}
+
+// The first line whose cells pass the test, for rules that don't decide a
+// line by one player filling it.
+pub fn line_where(
+    board: &Board,
+    is_complete: impl Fn(&[&Cell]) -> bool,
+) -> Option<Vec<(usize, usize)>> {
+    win_arrangements(board)
+        .iter()
+        .find(|cell_positions| is_complete(&Board::cell_positions_to_cells(cell_positions)))
+        .map(|cell_positions| {
+            cell_positions
+                .iter()
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect()
+        })
+}
+
+// Whether every cell holds a number and they add up to the target.
+pub fn sums_to(cells: &[&Cell], target: u8) -> bool {
+    let numbers: Option<Vec<u8>> = cells
+        .iter()
+        .map(|cell| match cell {
+            Cell::Number(number) => Some(*number),
+            _ => None,
+        })
+        .collect();
+    numbers.is_some_and(|numbers| numbers.iter().sum::<u8>() == target)
+}
+
+pub fn winner_on(board: &Board, variant: Variant) -> Option<Player> {
+    let line_player = winning_player_on(board)?;
+    match variant {
+        Variant::Misere => Some(line_player.opponent()),
+        _ => Some(line_player),
+    }
+}
+
+pub fn winning_players_on(board: &Board) -> Vec<Player> {
+    [Player::X, Player::O]
+        .iter()
+        .filter(|player| {
+            win_arrangements(board).iter().any(|cell_positions| {
+                let cells = Board::cell_positions_to_cells(cell_positions);
+                all_are_player(&cells, player)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
 pub fn board_full(board: &Board) -> bool {
     board.get_cells().iter().all(|cell| match cell {
-        Cell::Marker(_) => true,
+        Cell::Marker(_) | Cell::Symbol(_) | Cell::Blocked | Cell::Number(_) => true,
         Cell::Empty => false,
     })
 }
@@ -47,8 +107,8 @@ pub fn board_full(board: &Board) -> bool {
 #[allow(dead_code)]
 pub fn board_empty(board: &Board) -> bool {
     board.get_cells().iter().all(|cell| match cell {
-        Cell::Marker(_) => false,
-        Cell::Empty => true,
+        Cell::Marker(_) | Cell::Symbol(_) | Cell::Number(_) => false,
+        Cell::Empty | Cell::Blocked => true,
     })
 }
 
@@ -174,7 +234,9 @@ pub fn potential_fork_moves<'a>(
         .iter()
         .filter_map(|(cell, row_index, col_index)| {
             let mut imagined_board = Board::from(board);
-            imagined_board.set_cell_at(*row_index, *col_index, Cell::Marker(for_player.clone()));
+            imagined_board
+                .set_cell_at(*row_index, *col_index, Cell::Marker(for_player.clone()))
+                .ok()?;
             let score = potential_win_moves(&imagined_board, for_player).len();
             if score > 1 {
                 Some((*cell, *row_index, *col_index, score))
@@ -184,7 +246,7 @@ pub fn potential_fork_moves<'a>(
         })
         .collect::<Vec<(&Cell, usize, usize, usize)>>();
 
-    potential_forks_scored.sort_by(|a, b| (b.3).cmp(&a.3));
+    potential_forks_scored.sort_by_key(|&(_, _, _, score)| Reverse(score));
     potential_forks_scored
         .iter()
         .map(|&(cell, row_index, col_index, _)| (cell, row_index, col_index))
@@ -0,0 +1,85 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::multiplayer::{Multiplayer, Position};
+use crate::models::player::Player;
+use crate::utils::lines::Lines;
+
+// What a line of six is worth to a player once they have this many stones in
+// it and the other player has none. Four or five is a threat, because two
+// stones can finish it in a single turn.
+const THREAT_WEIGHTS: [i32; 7] = [0, 1, 10, 100, 5_000, 20_000, 1_000_000];
+// Stones are only worth putting down near the ones already there.
+const NEIGHBOURHOOD: usize = 2;
+
+fn candidates(board: &Board) -> Vec<Position> {
+    let cells = board.get_cell_positions();
+    let stones: Vec<Position> = cells
+        .iter()
+        .filter(|(cell, _, _)| matches!(cell, Cell::Marker(_)))
+        .map(|&(_, row_index, col_index)| (row_index, col_index))
+        .collect();
+    let open = cells
+        .iter()
+        .filter(|(cell, _, _)| **cell == Cell::Empty)
+        .map(|&(_, row_index, col_index)| (row_index, col_index));
+
+    if stones.is_empty() {
+        let size = board.size();
+        let center = (size.rows / 2, size.cols / 2);
+        return open.filter(|&cell| cell == center).collect();
+    }
+
+    open.filter(|&(row_index, col_index)| {
+        stones.iter().any(|&(stone_row, stone_col)| {
+            row_index.abs_diff(stone_row) <= NEIGHBOURHOOD
+                && col_index.abs_diff(stone_col) <= NEIGHBOURHOOD
+        })
+    })
+    .collect()
+}
+
+// A stone's worth is what it builds in lines still open to the mover plus,
+// a little less, what it spoils in lines still open to the other player.
+fn threat_score(board: &Board, lines: &Lines, position: Position, player: &Player) -> i32 {
+    let top = THREAT_WEIGHTS.len() - 1;
+    lines
+        .through(position)
+        .iter()
+        .map(|&line_index| match lines.marks(board, line_index, player) {
+            (own, 0) => THREAT_WEIGHTS[(own + 1).min(top)],
+            (0, other) => THREAT_WEIGHTS[(other + 1).min(top)] * 9 / 10,
+            _ => 0,
+        })
+        .sum()
+}
+
+// A line this turn's stones can finish outright, if there is one.
+fn winning_stones(
+    board: &Board,
+    lines: &Lines,
+    player: &Player,
+    count: usize,
+) -> Option<Vec<Position>> {
+    (0..lines.cells.len()).find_map(|line_index| {
+        let (_, other) = lines.marks(board, line_index, player);
+        let empty = lines.empty_cells(board, line_index);
+        (other == 0 && !empty.is_empty() && empty.len() <= count).then_some(empty)
+    })
+}
+
+// Finishes six when this turn's stones can, otherwise puts the stone where it
+// builds and blocks the most. A turn's second stone is picked the same way
+// once the first is down.
+pub fn best_connect6_stone(multiplayer: &Multiplayer, player: &Player) -> Option<Position> {
+    let count = multiplayer.marks_left_in_turn();
+    let board = &multiplayer.board;
+    let lines = Lines::of(board);
+
+    if let Some(stones) = winning_stones(board, &lines, player, count) {
+        return stones.first().copied();
+    }
+
+    candidates(board)
+        .into_iter()
+        .max_by_key(|&position| threat_score(board, &lines, position, player))
+}
@@ -0,0 +1,226 @@
+use crate::models::definition::{DefinitionProblem, VariantDefinition};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const DEFAULT_VARIANTS_DIR: &str = "variants";
+
+#[derive(Debug)]
+pub enum DefinitionError {
+    Unreadable(String, io::Error),
+    Toml(String, toml::de::Error),
+    Json(String, serde_json::Error),
+    Invalid(String, DefinitionProblem),
+    DuplicateName(String, String),
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DefinitionError::Unreadable(path, error) => {
+                write!(f, "Couldn't read the variant file {}: {}", path, error)
+            }
+            DefinitionError::Toml(path, error) => {
+                write!(f, "Invalid TOML in the variant file {}: {}", path, error)
+            }
+            DefinitionError::Json(path, error) => {
+                write!(f, "Invalid JSON in the variant file {}: {}", path, error)
+            }
+            DefinitionError::Invalid(path, problem) => {
+                write!(f, "The variant file {} doesn't work: {}", path, problem)
+            }
+            DefinitionError::DuplicateName(path, name) => write!(
+                f,
+                "The variant file {} uses the name '{}', which another file already has",
+                path, name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+// JSON if the file says so, TOML otherwise.
+pub fn parse_definition(path: &str, contents: &str) -> Result<VariantDefinition, DefinitionError> {
+    let is_json = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let definition: VariantDefinition = if is_json {
+        serde_json::from_str(contents)
+            .map_err(|error| DefinitionError::Json(String::from(path), error))?
+    } else {
+        toml::from_str(contents)
+            .map_err(|error| DefinitionError::Toml(String::from(path), error))?
+    };
+
+    definition
+        .validate()
+        .map_err(|problem| DefinitionError::Invalid(String::from(path), problem))?;
+    Ok(definition)
+}
+
+// Every .toml and .json file in the directory, in name order. A directory
+// that isn't there just has no variants in it. A file that can't be used is
+// left out and its error returned alongside the rest, so one bad file doesn't
+// get in the way of games that don't need it.
+pub fn load_definitions(dir: &str) -> (Vec<VariantDefinition>, Vec<DefinitionError>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return (vec![], vec![]),
+        Err(error) => {
+            return (
+                vec![],
+                vec![DefinitionError::Unreadable(String::from(dir), error)],
+            )
+        }
+    };
+
+    let mut paths: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| matches!(extension, "toml" | "json"))
+        })
+        .map(|path| path.display().to_string())
+        .collect();
+    paths.sort();
+
+    let mut definitions: Vec<VariantDefinition> = vec![];
+    let mut errors: Vec<DefinitionError> = vec![];
+    for path in paths {
+        let definition = fs::read_to_string(&path)
+            .map_err(|error| DefinitionError::Unreadable(path.clone(), error))
+            .and_then(|contents| parse_definition(&path, &contents));
+
+        match definition {
+            Ok(definition)
+                if definitions
+                    .iter()
+                    .any(|loaded| loaded.name.eq_ignore_ascii_case(&definition.name)) =>
+            {
+                errors.push(DefinitionError::DuplicateName(path, definition.name))
+            }
+            Ok(definition) => definitions.push(definition),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (definitions, errors)
+}
+
+pub fn find_definition<'a>(
+    definitions: &'a [VariantDefinition],
+    name: &str,
+) -> Option<&'a VariantDefinition> {
+    definitions
+        .iter()
+        .find(|definition| definition.name.eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::definition::Direction;
+
+    #[test]
+    fn reads_toml_and_json() {
+        let toml = parse_definition(
+            "rack.toml",
+            "name = \"rack\"\nrows = 4\ncols = 5\ngravity = true\n",
+        )
+        .unwrap();
+        assert_eq!(
+            (toml.rows, toml.cols, toml.gravity),
+            (Some(4), Some(5), true)
+        );
+
+        let json = parse_definition(
+            "lines.JSON",
+            r#"{ "name": "lines", "directions": ["horizontal", "anti-diagonal"] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            json.directions,
+            vec![Direction::Horizontal, Direction::AntiDiagonal]
+        );
+        assert_eq!(json.win_length, 3);
+    }
+
+    #[test]
+    fn rejects_files_that_dont_parse() {
+        let error = parse_definition("broken.toml", "name = ").unwrap_err();
+        assert!(matches!(error, DefinitionError::Toml(..)));
+        assert!(error
+            .to_string()
+            .starts_with("Invalid TOML in the variant file broken.toml:"));
+
+        let error = parse_definition("broken.json", "{ \"name\": ").unwrap_err();
+        assert!(matches!(error, DefinitionError::Json(..)));
+        assert!(error
+            .to_string()
+            .starts_with("Invalid JSON in the variant file broken.json:"));
+
+        let error =
+            parse_definition("extra.toml", "name = \"extra\"\ncolour = \"red\"\n").unwrap_err();
+        assert!(error.to_string().contains("unknown field `colour`"));
+
+        let error = parse_definition(
+            "sideways.toml",
+            "name = \"sideways\"\ndirections = [\"up\"]\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown variant `up`"));
+    }
+
+    #[test]
+    fn rejects_definitions_that_dont_validate() {
+        let error = parse_definition("taken.toml", "name = \"classic\"\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The variant file taken.toml doesn't work: \
+             'classic' is already the name of another variant"
+        );
+
+        let error = parse_definition("spaces.json", r#"{ "name": "two words" }"#).unwrap_err();
+        assert!(matches!(
+            error,
+            DefinitionError::Invalid(_, DefinitionProblem::NameHasSpaces(_))
+        ));
+
+        let error = parse_definition("schedule.toml", "name = \"schedule\"\nschedule = \"1/0\"\n")
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DefinitionError::Invalid(_, DefinitionProblem::NotASchedule(_))
+        ));
+    }
+
+    #[test]
+    fn skips_bad_files_and_keeps_the_rest() {
+        let dir = std::env::temp_dir().join(format!("variants-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a-good.toml"), "name = \"good\"\n").unwrap();
+        fs::write(dir.join("b-broken.toml"), "name = ").unwrap();
+        fs::write(dir.join("c-again.json"), r#"{ "name": "GOOD" }"#).unwrap();
+        fs::write(dir.join("notes.txt"), "not a variant").unwrap();
+
+        let (definitions, errors) = load_definitions(&dir.display().to_string());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].name, "good");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], DefinitionError::Toml(..)));
+        assert!(matches!(errors[1], DefinitionError::DuplicateName(..)));
+    }
+
+    #[test]
+    fn a_missing_directory_has_no_variants() {
+        let (definitions, errors) = load_definitions("no/such/variants/dir");
+        assert!(definitions.is_empty());
+        assert!(errors.is_empty());
+    }
+}
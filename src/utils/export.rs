@@ -0,0 +1,619 @@
+use crate::models::board::{Board, BoardSize};
+use crate::models::game_record::{
+    GameMetadata, GameRecord, GameResult, Participant, RecordedMove, Termination, DEFAULT_STRATEGY,
+};
+use crate::models::gomoku::{GomokuRule, Opening};
+use crate::models::player::Player;
+use crate::models::turn_schedule::TurnSchedule;
+use crate::models::variant::Variant;
+use crate::utils::notation::{
+    cell_name, cube_cell_name, number_cell_name, parse_cell_name, parse_cube_cell_name,
+    parse_number_cell_name, parse_sized_cell_name, parse_symbol_cell_name, read_position,
+    sized_cell_name, symbol_cell_name,
+};
+use crate::utils::validation::count_markers;
+use regex::Regex;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordFormat {
+    Json,
+    Pgn,
+}
+
+#[derive(Debug)]
+pub enum RecordError {
+    Json(serde_json::Error),
+    Pgn(String),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordError::Json(error) => write!(f, "Invalid JSON game record: {}", error),
+            RecordError::Pgn(message) => write!(f, "Invalid PGN game record: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl From<serde_json::Error> for RecordError {
+    fn from(error: serde_json::Error) -> Self {
+        RecordError::Json(error)
+    }
+}
+
+impl RecordFormat {
+    pub fn from_path(path: &str) -> RecordFormat {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("pgn") | Some("txt") => RecordFormat::Pgn,
+            _ => RecordFormat::Json,
+        }
+    }
+}
+
+fn pgn_error(message: &str) -> RecordError {
+    RecordError::Pgn(String::from(message))
+}
+
+// Inside a tag's quotes, PGN escapes quotes and backslashes with a backslash.
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+// Marks already on the board of a game set up from a position. The moves
+// after them are numbered and given to players as if the game had been played
+// from an empty board.
+fn marks_before(start_position: Option<&str>) -> Result<usize, RecordError> {
+    match start_position {
+        Some(position) => {
+            let (board, _) = read_position(position)
+                .map_err(|_| RecordError::Pgn(format!("Bad [Position] tag '{}'", position)))?;
+            Ok(count_markers(&board, &Player::X) + count_markers(&board, &Player::O))
+        }
+        None => Ok(0),
+    }
+}
+
+pub fn to_json(record: &GameRecord) -> String {
+    serde_json::to_string(record).unwrap()
+}
+
+pub fn from_json(json: &str) -> Result<GameRecord, RecordError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+fn participant_tag(participant: &Participant) -> &str {
+    match participant {
+        Participant::Human => "Human",
+        Participant::Computer { .. } => "Computer",
+    }
+}
+
+// Each seat's score in turn order, so two players get the usual "1-0" and
+// "1/2-1/2" and three get "0-1-0" or "1/3-1/3-1/3".
+fn result_tag(result: &GameResult, player_count: usize) -> String {
+    let score = |seat: &Player| match result {
+        GameResult::Winner(winner) if winner == seat => String::from("1"),
+        GameResult::Winner(_) => String::from("0"),
+        _ => format!("1/{}", player_count),
+    };
+
+    match result {
+        GameResult::Unfinished => String::from("*"),
+        _ => Player::seats(player_count)
+            .iter()
+            .map(score)
+            .collect::<Vec<String>>()
+            .join("-"),
+    }
+}
+
+fn parse_result_tag(tag: &str, player_count: usize) -> Option<GameResult> {
+    Player::seats(player_count)
+        .iter()
+        .map(|seat| GameResult::Winner(seat.clone()))
+        .chain([GameResult::Draw, GameResult::Unfinished])
+        .find(|result| result_tag(result, player_count) == tag)
+}
+
+fn parse_player_tag(tag: &str) -> Option<Player> {
+    Player::ALL
+        .iter()
+        .find(|player| player.character() == tag)
+        .cloned()
+}
+
+fn move_token(recorded: &RecordedMove) -> String {
+    let (row_index, col_index) = (recorded.row_index, recorded.col_index);
+    if let Some(number) = recorded.number {
+        return number_cell_name(number, row_index, col_index);
+    }
+    if let Some(size) = recorded.size {
+        return sized_cell_name(size, row_index, col_index);
+    }
+
+    let token = match (
+        recorded.layer_index,
+        recorded.symbol,
+        recorded.from,
+        recorded.pair,
+    ) {
+        (Some(layer_index), _, _, _) => cube_cell_name((layer_index, row_index, col_index)),
+        (None, Some(symbol), _, _) => symbol_cell_name(symbol, row_index, col_index),
+        (None, None, Some((from_row, from_col)), _) => format!(
+            "{}-{}",
+            cell_name(from_row, from_col),
+            cell_name(row_index, col_index)
+        ),
+        (None, None, None, Some((pair_row, pair_col))) => format!(
+            "{}~{}",
+            cell_name(row_index, col_index),
+            cell_name(pair_row, pair_col)
+        ),
+        (None, None, None, None) => cell_name(row_index, col_index),
+    };
+
+    match recorded.collapse {
+        Some((landed_row, landed_col)) => {
+            format!("{}>{}", token, cell_name(landed_row, landed_col))
+        }
+        None => token,
+    }
+}
+
+fn parse_move_token(token: &str, player: &Player) -> Result<RecordedMove, RecordError> {
+    let not_a_cell = || RecordError::Pgn(format!("'{}' is not a cell", token));
+    let mut recorded = RecordedMove {
+        player: player.clone(),
+        layer_index: None,
+        row_index: 0,
+        col_index: 0,
+        symbol: None,
+        from: None,
+        pair: None,
+        collapse: None,
+        number: None,
+        size: None,
+    };
+
+    let token = match token.split_once('>') {
+        Some((token, landed)) => {
+            recorded.collapse = Some(parse_cell_name(landed).ok_or_else(not_a_cell)?);
+            token
+        }
+        None => token,
+    };
+
+    let (row_index, col_index) =
+        if let Some((number, row_index, col_index)) = parse_number_cell_name(token) {
+            recorded.number = Some(number);
+            (row_index, col_index)
+        } else if let Some((size, row_index, col_index)) = parse_sized_cell_name(token) {
+            recorded.size = Some(size);
+            (row_index, col_index)
+        } else if let Some((layer_index, row_index, col_index)) = parse_cube_cell_name(token) {
+            recorded.layer_index = Some(layer_index);
+            (row_index, col_index)
+        } else if let Some((symbol, row_index, col_index)) = parse_symbol_cell_name(token) {
+            recorded.symbol = Some(symbol);
+            (row_index, col_index)
+        } else if let Some((from, to)) = token.split_once('-') {
+            recorded.from = Some(parse_cell_name(from).ok_or_else(not_a_cell)?);
+            parse_cell_name(to).ok_or_else(not_a_cell)?
+        } else if let Some((first, second)) = token.split_once('~') {
+            recorded.pair = Some(parse_cell_name(second).ok_or_else(not_a_cell)?);
+            parse_cell_name(first).ok_or_else(not_a_cell)?
+        } else {
+            parse_cell_name(token).ok_or_else(not_a_cell)?
+        };
+
+    recorded.row_index = row_index;
+    recorded.col_index = col_index;
+    Ok(recorded)
+}
+
+pub fn to_pgn(record: &GameRecord) -> String {
+    let metadata = &record.metadata;
+    let mut tags = vec![
+        (String::from("Date"), metadata.date.replace('-', ".")),
+        (
+            String::from("Variant"),
+            String::from(metadata.variant.name()),
+        ),
+    ];
+
+    let seats = Player::seats(metadata.player_count());
+    for player in seats {
+        tags.push((
+            String::from(player.character()),
+            String::from(participant_tag(metadata.participant(player))),
+        ));
+    }
+    for player in seats {
+        if let Participant::Computer { strategy } = metadata.participant(player) {
+            tags.push((format!("{}Strategy", player.character()), strategy.clone()));
+        }
+    }
+
+    tags.push((
+        String::from("FirstPlayer"),
+        String::from(metadata.first_player.character()),
+    ));
+    if let Some(position) = &metadata.start_position {
+        tags.push((String::from("Position"), position.clone()));
+    }
+    if let Some(board_size) = &metadata.board_size {
+        tags.push((String::from("Board"), board_size.to_string()));
+    }
+    if let Some(board_count) = metadata.board_count {
+        tags.push((String::from("Boards"), board_count.to_string()));
+    }
+    if let Some(shape) = &metadata.shape {
+        tags.push((String::from("Shape"), shape.clone()));
+    }
+    if metadata.toroidal {
+        tags.push((String::from("Topology"), String::from("torus")));
+    }
+    if let Some(rule) = metadata.rule {
+        tags.push((String::from("Rule"), String::from(rule.name())));
+    }
+    if let Some(opening) = metadata.opening {
+        tags.push((String::from("Opening"), String::from(opening.name())));
+    }
+    if let Some(schedule) = metadata.schedule {
+        tags.push((String::from("Schedule"), schedule.to_string()));
+    }
+    if let Some(definition) = &metadata.definition {
+        tags.push((String::from("Definition"), definition.clone()));
+    }
+    tags.push((
+        String::from("Result"),
+        result_tag(&metadata.result, metadata.player_count()),
+    ));
+    tags.push((
+        String::from("Termination"),
+        String::from(metadata.termination.description()),
+    ));
+
+    let mut pgn: String = tags
+        .iter()
+        .map(|(name, value)| format!("[{} \"{}\"]\n", name, escape_tag_value(value)))
+        .collect();
+    pgn.push('\n');
+
+    // One numbered entry per round of the table, however many marks each
+    // turn in it placed. A game set up from a position can start partway
+    // through a round, which is written like '1... B2'.
+    let schedule = metadata.schedule.unwrap_or_default();
+    let player_count = metadata.player_count();
+    let marks_before = marks_before(metadata.start_position.as_deref()).unwrap_or(0);
+    let mut rounds: Vec<(usize, Vec<String>)> = vec![];
+    for (index, recorded) in record.moves.iter().enumerate() {
+        let round = schedule.round(marks_before + index, player_count);
+        if rounds.last().map(|(last_round, _)| *last_round) != Some(round) {
+            rounds.push((round, vec![]));
+        }
+        if let Some((_, cells)) = rounds.last_mut() {
+            cells.push(move_token(recorded));
+        }
+    }
+    let mut movetext: Vec<String> = rounds
+        .iter()
+        .enumerate()
+        .map(|(index, (round, cells))| {
+            let continued = index == 0
+                && marks_before > 0
+                && schedule.round(marks_before - 1, player_count) == *round;
+            let dots = if continued { "..." } else { "." };
+            format!("{}{} {}", round + 1, dots, cells.join(" "))
+        })
+        .collect();
+    movetext.push(result_tag(&metadata.result, metadata.player_count()));
+
+    pgn.push_str(&movetext.join(" "));
+    pgn.push('\n');
+    pgn
+}
+
+pub fn from_pgn(pgn: &str) -> Result<GameRecord, RecordError> {
+    let tag_regex = Regex::new(r#"^\s*\[(\w+)\s+"((?:[^"\\]|\\.)*)"\]\s*$"#).unwrap();
+    let move_number_regex = Regex::new(r"^\d+\.(\.\.)?$").unwrap();
+
+    let mut tags: Vec<(String, String)> = vec![];
+    let mut tokens: Vec<&str> = vec![];
+
+    for line in pgn.lines() {
+        if let Some(captures) = tag_regex.captures(line) {
+            tags.push((String::from(&captures[1]), unescape_tag_value(&captures[2])));
+        } else {
+            tokens.extend(line.split_whitespace());
+        }
+    }
+
+    let tag = |name: &str| {
+        tags.iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| RecordError::Pgn(format!("Missing [{}] tag", name)))
+    };
+
+    let participant = |player: &Player| -> Result<Participant, RecordError> {
+        match tag(player.character())? {
+            "Human" => Ok(Participant::Human),
+            "Computer" => {
+                let strategy_tag = format!("{}Strategy", player.character());
+                let strategy = tag(&strategy_tag).unwrap_or(DEFAULT_STRATEGY);
+                Ok(Participant::Computer {
+                    strategy: String::from(strategy),
+                })
+            }
+            other => Err(RecordError::Pgn(format!("Unknown participant '{}'", other))),
+        }
+    };
+
+    // Custom variants aren't among the built-in names, so they're only
+    // known by the variant file they name.
+    let definition = tag("Definition").ok().map(String::from);
+    let variant = match tag("Variant") {
+        Ok(name) if definition.is_some() && Variant::Custom.name().eq_ignore_ascii_case(name) => {
+            Variant::Custom
+        }
+        Ok(name) => Variant::from_name(name)
+            .ok_or_else(|| RecordError::Pgn(format!("Unknown variant '{}'", name)))?,
+        Err(_) => Variant::Classic,
+    };
+    let others = Player::ALL[2..]
+        .iter()
+        .take_while(|player| tag(player.character()).is_ok())
+        .map(participant)
+        .collect::<Result<Vec<Participant>, RecordError>>()?;
+    let player_count = 2 + others.len();
+
+    let first_player =
+        parse_player_tag(tag("FirstPlayer")?).ok_or_else(|| pgn_error("Bad [FirstPlayer] tag"))?;
+    let result = parse_result_tag(tag("Result")?, player_count)
+        .ok_or_else(|| pgn_error("Bad [Result] tag"))?;
+    let termination = Termination::from_description(tag("Termination")?)
+        .ok_or_else(|| pgn_error("Bad [Termination] tag"))?;
+
+    let board_size = match tag("Board") {
+        Ok(size) => Some(
+            BoardSize::parse(size)
+                .ok_or_else(|| RecordError::Pgn(format!("Bad [Board] tag '{}'", size)))?,
+        ),
+        Err(_) => None,
+    };
+
+    let shape = match tag("Shape") {
+        Ok(shape) => {
+            let win_length =
+                board_size.map_or(BoardSize::CLASSIC.win_length, |size| size.win_length);
+            Board::from_mask(shape, win_length)
+                .ok_or_else(|| RecordError::Pgn(format!("Bad [Shape] tag '{}'", shape)))?;
+            Some(String::from(shape))
+        }
+        Err(_) => None,
+    };
+
+    let schedule = match tag("Schedule") {
+        Ok(schedule) => Some(
+            TurnSchedule::parse(schedule)
+                .ok_or_else(|| RecordError::Pgn(format!("Bad [Schedule] tag '{}'", schedule)))?,
+        ),
+        Err(_) => None,
+    };
+
+    let marks_before = marks_before(tag("Position").ok())?;
+    let mut moves = vec![];
+    for token in tokens {
+        if move_number_regex.is_match(token) {
+            continue;
+        }
+
+        if let Some(movetext_result) = parse_result_tag(token, player_count) {
+            if movetext_result != result {
+                return Err(pgn_error("Movetext result disagrees with [Result] tag"));
+            }
+            break;
+        }
+
+        let player = schedule.unwrap_or_default().mover(
+            marks_before + moves.len(),
+            &first_player,
+            player_count,
+        );
+        moves.push(parse_move_token(token, &player)?);
+    }
+
+    Ok(GameRecord {
+        metadata: GameMetadata {
+            variant,
+            x: participant(&Player::X)?,
+            o: participant(&Player::O)?,
+            others,
+            first_player,
+            start_position: tag("Position").ok().map(String::from),
+            board_size,
+            board_count: match tag("Boards") {
+                Ok(count) => Some(count.parse().map_err(|_| pgn_error("Bad [Boards] tag"))?),
+                Err(_) => None,
+            },
+            shape,
+            toroidal: match tag("Topology") {
+                Ok("torus") => true,
+                Ok("flat") | Err(_) => false,
+                Ok(_) => return Err(pgn_error("Bad [Topology] tag")),
+            },
+            rule: match tag("Rule") {
+                Ok(name) => Some(
+                    GomokuRule::from_name(name)
+                        .ok_or_else(|| RecordError::Pgn(format!("Bad [Rule] tag '{}'", name)))?,
+                ),
+                Err(_) => None,
+            },
+            opening: match tag("Opening") {
+                Ok(name) => Some(
+                    Opening::from_name(name)
+                        .ok_or_else(|| RecordError::Pgn(format!("Bad [Opening] tag '{}'", name)))?,
+                ),
+                Err(_) => None,
+            },
+            schedule,
+            definition,
+            date: tag("Date")?.replace('.', "-"),
+            result,
+            termination,
+        },
+        moves,
+    })
+}
+
+pub fn serialize(record: &GameRecord, format: RecordFormat) -> String {
+    match format {
+        RecordFormat::Json => to_json(record),
+        RecordFormat::Pgn => to_pgn(record),
+    }
+}
+
+// JSON records are stored one per line; a PGN record starts at the first tag
+// line following the previous record's movetext.
+pub fn parse_all(contents: &str, format: RecordFormat) -> Result<Vec<GameRecord>, RecordError> {
+    match format {
+        RecordFormat::Json => contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(from_json)
+            .collect(),
+        RecordFormat::Pgn => {
+            let mut games: Vec<String> = vec![];
+            let mut in_movetext = false;
+
+            for line in contents.lines() {
+                let is_tag = line.trim_start().starts_with('[');
+                if games.is_empty() || (is_tag && in_movetext) {
+                    games.push(String::new());
+                    in_movetext = false;
+                }
+                in_movetext = in_movetext || (!is_tag && !line.trim().is_empty());
+
+                let game = games.last_mut().unwrap();
+                game.push_str(line);
+                game.push('\n');
+            }
+
+            games
+                .iter()
+                .filter(|game| !game.trim().is_empty())
+                .map(|game| from_pgn(game))
+                .collect()
+        }
+    }
+}
+
+pub fn append_record(path: &str, record: &GameRecord) -> Result<(), io::Error> {
+    let format = RecordFormat::from_path(path);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serialize(record, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished_game() -> GameRecord {
+        let mut record = GameRecord::new(
+            Variant::Classic,
+            Participant::Human,
+            Participant::computer("heuristic"),
+            &Player::X,
+        );
+        for (player, row_index, col_index) in [
+            (Player::X, 0, 0),
+            (Player::O, 1, 1),
+            (Player::X, 0, 1),
+            (Player::O, 2, 2),
+            (Player::X, 0, 2),
+        ] {
+            record.push_move(&player, row_index, col_index);
+        }
+        record.finish(GameResult::Winner(Player::X), Termination::ThreeInARow);
+        record
+    }
+
+    #[test]
+    fn pgn_tag_values_with_quotes_and_backslashes_round_trip() {
+        let record = GameRecord::new(
+            Variant::Classic,
+            Participant::computer(r#"say "hi" \o/"#),
+            Participant::Human,
+            &Player::X,
+        );
+
+        let pgn = to_pgn(&record);
+        assert!(pgn.contains(r#"[XStrategy "say \"hi\" \\o/"]"#));
+        assert_eq!(from_pgn(&pgn).unwrap(), record);
+    }
+
+    #[test]
+    fn pgn_moves_from_a_start_position_go_to_the_side_to_move() {
+        let mut record = GameRecord::new(
+            Variant::Classic,
+            Participant::Human,
+            Participant::Human,
+            &Player::X,
+        );
+        record.metadata.start_position = Some(String::from("X__/___/___ o"));
+        record.push_move(&Player::O, 1, 1);
+        record.push_move(&Player::X, 2, 2);
+        record.push_move(&Player::O, 0, 2);
+
+        let pgn = to_pgn(&record);
+        assert!(pgn.contains("1... B2 2. C3 A3 *"));
+        assert_eq!(from_pgn(&pgn).unwrap(), record);
+    }
+
+    #[test]
+    fn json_records_round_trip() {
+        let record = finished_game();
+        assert_eq!(from_json(&to_json(&record)).unwrap(), record);
+    }
+
+    #[test]
+    fn pgn_records_round_trip() {
+        let record = finished_game();
+        let pgn = to_pgn(&record);
+        assert!(pgn.contains("1. A1 B2 2. A2 C3 3. A3 1-0"));
+        assert_eq!(from_pgn(&pgn).unwrap(), record);
+    }
+
+    #[test]
+    fn several_pgn_records_in_one_file_parse_separately() {
+        let record = finished_game();
+        let contents = format!("{}\n{}", to_pgn(&record), to_pgn(&record));
+        let records = parse_all(&contents, RecordFormat::Pgn).unwrap();
+        assert_eq!(records, vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn pgn_movetext_that_disagrees_with_the_result_is_rejected() {
+        let pgn = to_pgn(&finished_game()).replace("3. A3 1-0", "3. A3 0-1");
+        assert!(from_pgn(&pgn).is_err());
+    }
+}
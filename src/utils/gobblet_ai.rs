@@ -0,0 +1,33 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::player::Player;
+use crate::utils::ai::win_arrangements;
+
+pub const SEARCH_DEPTH: usize = 3;
+// What a line is worth to a player by how many of its tops they hold, when
+// none of the others are the opponent's.
+const LINE_WEIGHTS: [i32; 3] = [0, 1, 10];
+
+pub fn line_score(board: &Board, player: &Player) -> i32 {
+    let own = Cell::Marker(player.clone());
+    let theirs = Cell::Marker(player.opponent());
+
+    win_arrangements(board)
+        .iter()
+        .map(|cell_positions| {
+            let own_count = cell_positions
+                .iter()
+                .filter(|(cell, _, _)| **cell == own)
+                .count();
+            let their_count = cell_positions
+                .iter()
+                .filter(|(cell, _, _)| **cell == theirs)
+                .count();
+            match (own_count, their_count) {
+                (count, 0) if count < LINE_WEIGHTS.len() => LINE_WEIGHTS[count],
+                (0, count) if count < LINE_WEIGHTS.len() => -LINE_WEIGHTS[count],
+                _ => 0,
+            }
+        })
+        .sum()
+}
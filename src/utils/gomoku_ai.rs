@@ -0,0 +1,127 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::gomoku::{Gomoku, Position, BLACK, WHITE};
+use crate::models::player::Player;
+use crate::models::rules::Rules;
+use crate::utils::lines::Lines;
+
+// What a line of five is worth to a player once they have this many stones
+// in it and the other player has none.
+const THREAT_WEIGHTS: [i32; 6] = [0, 1, 12, 150, 2_000, 100_000];
+// Whoever moves next after the opening is a stone up in all but name.
+const TEMPO: i32 = 24;
+// Stones are only worth putting down near the ones already there.
+const NEIGHBOURHOOD: usize = 2;
+
+// Black's lines less White's: above zero means Black is ahead.
+fn balance(board: &Board, lines: &Lines) -> i32 {
+    (0..lines.cells.len())
+        .map(|line_index| match lines.marks(board, line_index, &BLACK) {
+            (black, 0) => THREAT_WEIGHTS[black.min(5)],
+            (0, white) => -THREAT_WEIGHTS[white.min(5)],
+            _ => 0,
+        })
+        .sum()
+}
+
+fn candidates(gomoku: &Gomoku) -> Vec<Position> {
+    let open = gomoku.open_positions();
+    let stones: Vec<Position> = gomoku
+        .board
+        .get_cell_positions()
+        .iter()
+        .filter(|(cell, _, _)| matches!(cell, Cell::Marker(_)))
+        .map(|&(_, row_index, col_index)| (row_index, col_index))
+        .collect();
+
+    if stones.is_empty() {
+        let size = gomoku.board.size();
+        let center = (size.rows / 2, size.cols / 2);
+        return open.into_iter().filter(|&cell| cell == center).collect();
+    }
+
+    open.into_iter()
+        .filter(|&(row_index, col_index)| {
+            stones.iter().any(|&(stone_row, stone_col)| {
+                row_index.abs_diff(stone_row) <= NEIGHBOURHOOD
+                    && col_index.abs_diff(stone_col) <= NEIGHBOURHOOD
+            })
+        })
+        .collect()
+}
+
+// A stone's worth is what it builds in lines still open to the mover plus,
+// a little less, what it spoils in lines still open to the other player.
+fn threat_score(board: &Board, lines: &Lines, position: Position, player: &Player) -> i32 {
+    lines
+        .through(position)
+        .iter()
+        .map(|&line_index| match lines.marks(board, line_index, player) {
+            (own, 0) => THREAT_WEIGHTS[(own + 1).min(5)],
+            (0, other) => THREAT_WEIGHTS[(other + 1).min(5)] * 9 / 10,
+            _ => 0,
+        })
+        .sum()
+}
+
+// Wins on the spot when it can, otherwise plays the legal stone that builds
+// and blocks the most.
+pub fn best_gomoku_move(gomoku: &Gomoku, player: &Player) -> Option<Position> {
+    let lines = Lines::of(&gomoku.board);
+    let legal: Vec<Position> = candidates(gomoku)
+        .into_iter()
+        .filter(|&position| gomoku.forbidden(player, position).is_none())
+        .collect();
+
+    if let Some(&winning) = legal
+        .iter()
+        .find(|&&position| gomoku.wins_at(player, position))
+    {
+        return Some(winning);
+    }
+
+    legal
+        .into_iter()
+        .max_by_key(|&position| threat_score(&gomoku.board, &lines, position, player))
+}
+
+// An opening stone for swap rules, where whoever lays the opening wants the
+// position as even as possible so that either colour is as good to them.
+pub fn balanced_opening_stone(gomoku: &Gomoku) -> Option<Position> {
+    let lines = Lines::of(&gomoku.board);
+    let stone = gomoku.current_player();
+    let size = gomoku.board.size();
+    let center = (size.rows / 2, size.cols / 2);
+    // After a black stone White moves next, and the other way round.
+    let even = if stone == BLACK { TEMPO } else { -TEMPO };
+
+    gomoku
+        .open_positions()
+        .into_iter()
+        .filter(|&(row_index, col_index)| {
+            row_index.abs_diff(center.0) <= NEIGHBOURHOOD
+                && col_index.abs_diff(center.1) <= NEIGHBOURHOOD
+        })
+        .min_by_key(|&position| {
+            let mut board = gomoku.board.clone();
+            board.rows[position.0].cells[position.1] = Cell::Marker(stone.clone());
+            (balance(&board, &lines) - even).abs()
+        })
+}
+
+// The colour worth taking after a swap opening, allowing for White moving
+// next.
+pub fn preferred_colour(gomoku: &Gomoku) -> Player {
+    let lines = Lines::of(&gomoku.board);
+    let tempo = if gomoku.current_player() == WHITE {
+        TEMPO
+    } else {
+        -TEMPO
+    };
+
+    if balance(&gomoku.board, &lines) > tempo {
+        BLACK
+    } else {
+        WHITE
+    }
+}
@@ -1,10 +1,26 @@
+use crate::error::Error;
 use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::cube::Cube;
+use crate::models::custom::Custom;
+use crate::models::definition::Direction;
+use crate::models::game_record::{GameMetadata, GameRecord, GameResult, Participant};
+use crate::models::gobblet::Gobblet;
+use crate::models::gomoku::{GomokuRule, Opening};
+use crate::models::meta_board::MetaBoard;
+use crate::models::notakto::Notakto;
+use crate::models::numerical::{Numerical, TARGET_SUM};
+use crate::models::order_chaos::Role;
 use crate::models::player::Player;
+use crate::models::quantum::Quantum;
+use crate::utils::ai::winning_player_on;
+use crate::utils::notation::{cell_name, row_letter};
 use std::io::{self, Write};
 
-pub fn clear_screen() {
+pub fn clear_screen() -> Result<(), Error> {
     print!("{clear_char}[2J{clear_char}[1;1H", clear_char = 27 as char);
-    io::stdout().flush().unwrap();
+    io::stdout().flush()?;
+    Ok(())
 }
 
 pub fn render_empty_lines(count: usize) {
@@ -18,35 +34,498 @@ pub fn render_current_player(player: &Player) {
 }
 
 pub fn render_board(board: &Board) {
-    println!("   ,---,---,---,");
+    render_board_with_drop(board, None);
+}
+
+fn render_grid(board: &Board, marker: impl Fn(usize, usize, &Cell) -> String) {
+    render_wide_grid(board, 1, marker);
+}
+
+// Like `render_grid`, but each cell has room for `width` characters.
+fn render_wide_grid(board: &Board, width: usize, marker: impl Fn(usize, usize, &Cell) -> String) {
+    let col_count = board.size().cols;
+    let edge = "-".repeat(width + 2);
+    println!("   ,{}", format!("{},", edge).repeat(col_count));
 
     for (index, row) in board.rows.iter().enumerate() {
         if index > 0 {
-            println!("   |---+---+---|");
+            println!(
+                "   |{}{}|",
+                format!("{}+", edge).repeat(col_count - 1),
+                edge
+            );
         }
 
-        let markers: Vec<&str> = row.cells.iter().map(|cell| cell.character()).collect();
-        let row_letter = match index {
-            0 => 'A',
-            1 => 'B',
-            2 => 'C',
-            _ => '?', // should never happen
-        };
+        let markers: String = row
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(col_index, cell)| {
+                format!(
+                    " {:<width$} |",
+                    marker(index, col_index, cell),
+                    width = width
+                )
+            })
+            .collect();
 
-        println!(
-            " {} | {} | {} | {} |",
-            row_letter, markers[0], markers[1], markers[2]
-        );
+        println!(" {} |{}", row_letter(index), markers);
     }
 
-    println!("   '---'---'---'");
-    println!("     1   2   3");
+    println!("   '{}", format!("{}'", edge).repeat(col_count));
+    let (left, right) = (3 + (width - 1) / 2, width - (width - 1) / 2);
+    let numbers: String = (1..=col_count)
+        .map(|col| format!("{:>left$}{:right$}", col, "", left = left, right = right))
+        .collect();
+    println!("   {}", numbers.trim_end());
+}
+
+// Each cell shows its top piece by owner and size, and how many pieces are
+// hidden under it.
+pub fn render_stacks(gobblet: &Gobblet) {
+    render_wide_grid(&gobblet.top_board(), 4, |row_index, col_index, _| {
+        let stack = &gobblet.stacks[row_index][col_index];
+        match stack.last() {
+            Some(piece) if stack.len() > 1 => format!(
+                "{}{}+{}",
+                piece.player.character(),
+                piece.size.letter(),
+                stack.len() - 1
+            ),
+            Some(piece) => format!("{}{}", piece.player.character(), piece.size.letter()),
+            None => String::new(),
+        }
+    });
+}
+
+pub fn render_reserves(gobblet: &Gobblet) {
+    for player in [Player::X, Player::O] {
+        let sizes: Vec<String> = gobblet
+            .reserve(&player)
+            .iter()
+            .map(|size| size.letter().to_string())
+            .collect();
+        if sizes.is_empty() {
+            println!("{} has no pieces left off the board", player.character());
+        } else {
+            println!("{} has left: {}", player.character(), sizes.join(" "));
+        }
+    }
+    println!("S, M and L are the sizes; +1 means one piece is hidden underneath.");
+}
+
+// With gravity the last marker is shown falling: an arrow over its column and
+// a trail through the empty cells it fell past.
+pub fn render_board_with_drop(board: &Board, last_drop: Option<(usize, usize)>) {
+    if let Some((_, drop_col)) = last_drop {
+        println!("{}v", " ".repeat(5 + drop_col * 4));
+    }
+
+    render_grid(board, |row_index, col_index, cell| {
+        match (cell, last_drop) {
+            (Cell::Empty, Some((drop_row, drop_col)))
+                if col_index == drop_col && row_index < drop_row =>
+            {
+                String::from(":")
+            }
+            _ => String::from(cell.character()),
+        }
+    });
+}
+
+// Marks about to disappear are dimmed to lowercase.
+pub fn render_board_with_fading(board: &Board, fading: &[(usize, usize)]) {
+    render_grid(board, |row_index, col_index, cell| {
+        if fading.contains(&(row_index, col_index)) {
+            cell.character().to_lowercase()
+        } else {
+            String::from(cell.character())
+        }
+    });
 }
 
 pub fn render_winning_player(player: &Player) {
     println!("Player {} won!", player.character());
 }
 
+pub fn render_split_win(winner: &Player, runner_up: &Player) {
+    println!(
+        "Player {} won! Player {} finished a line later and scores half.",
+        winner.character(),
+        runner_up.character()
+    );
+}
+
+pub fn render_seats(metadata: &GameMetadata) {
+    let seats: Vec<String> = Player::seats(metadata.player_count())
+        .iter()
+        .map(|player| match metadata.participant(player) {
+            Participant::Human => format!("{}: human", player.character()),
+            Participant::Computer { .. } => format!("{}: computer", player.character()),
+        })
+        .collect();
+    println!("{}", seats.join(", "));
+}
+
+pub fn render_roles(order: &Player) {
+    println!(
+        "Order: {}, Chaos: {}",
+        order.character(),
+        order.opponent().character()
+    );
+}
+
+pub fn render_winning_role(role: Role, player: &Player) {
+    let how = match role {
+        Role::Order => "made five in a row",
+        Role::Chaos => "filled the board",
+    };
+    println!("{} ({}) {} and won!", role.name(), player.character(), how);
+}
+
+pub fn render_gomoku_rules(rule: GomokuRule, opening: Option<Opening>) {
+    match opening {
+        Some(opening) if opening != Opening::Plain => {
+            println!("Rules: {} ({} opening)", rule.description(), opening.name())
+        }
+        _ => println!("Rules: {}", rule.description()),
+    }
+}
+
+pub fn render_winning_line(line: &[(usize, usize)]) {
+    let cells: Vec<String> = line
+        .iter()
+        .map(|&(row_index, col_index)| cell_name(row_index, col_index))
+        .collect();
+    println!("Winning line: {}", cells.join(" "));
+}
+
+pub fn render_found_mark((row_index, col_index): (usize, usize)) {
+    println!(
+        "There's already a mark on {}. Now you know, so try again!",
+        cell_name(row_index, col_index)
+    );
+}
+
+// Players are told when the other side bumps into one of their marks.
+pub fn render_marks_found_by(player: &Player, cells: &[(usize, usize)]) {
+    if cells.is_empty() {
+        return;
+    }
+
+    let names: Vec<String> = cells
+        .iter()
+        .map(|&(row_index, col_index)| cell_name(row_index, col_index))
+        .collect();
+    println!(
+        "Player {} has found your marks on {}.",
+        player.character(),
+        names.join(" ")
+    );
+}
+
+pub fn render_numbers_left(numerical: &Numerical) {
+    for player in [numerical.odd.clone(), numerical.odd.opponent()] {
+        let numbers: Vec<String> = numerical
+            .remaining(&player)
+            .iter()
+            .map(|number| number.to_string())
+            .collect();
+        println!("{} has: {}", player.character(), numbers.join(" "));
+    }
+    println!("A full line adding up to {} wins.", TARGET_SUM);
+}
+
+// What the variant file asks for, and what the players have left to do it.
+pub fn render_custom_rules(custom: &Custom) {
+    let definition = custom.definition;
+    println!("Rules: {}", definition.description());
+    let goal = if definition.misere { "lose" } else { "win" };
+    let directions: Vec<&str> = definition
+        .directions
+        .iter()
+        .map(|direction| direction.name())
+        .collect();
+    if definition.directions.len() == Direction::ALL.len() {
+        println!("{} in a row to {}.", definition.win_length, goal);
+    } else {
+        println!(
+            "{} in a row to {}, only {}.",
+            definition.win_length,
+            goal,
+            directions.join(" or ")
+        );
+    }
+    if definition.gravity {
+        println!("Marks fall to the bottom of their column.");
+    }
+
+    for player in [Player::X, Player::O] {
+        if let Some(pieces) = custom.pieces_left(&player) {
+            println!("{} has {} pieces left", player.character(), pieces);
+        }
+    }
+    if custom.marks_left_in_turn() > 1 {
+        println!("{} marks to place this turn", custom.marks_left_in_turn());
+    }
+}
+
+pub fn render_losing_player(player: &Player) {
+    println!("Player {} completed a line and lost!", player.character());
+}
+
 pub fn render_stalemate() {
     println!("STALEMATE!");
 }
+
+pub fn render_repetition() {
+    println!("DRAW! The same position came up three times.");
+}
+
+pub fn render_move_limit(move_limit: usize) {
+    println!("DRAW! Nobody won within {} moves.", move_limit);
+}
+
+pub fn render_record_summary(record: &GameRecord) {
+    let metadata = &record.metadata;
+    let describe = |participant: &Participant| match participant {
+        Participant::Human => String::from("human"),
+        Participant::Computer { strategy } => format!("computer ({})", strategy),
+    };
+    let result = match &metadata.result {
+        GameResult::Winner(player) => format!("Player {} won", player.character()),
+        GameResult::Draw => String::from("Draw"),
+        GameResult::Unfinished => String::from("Unfinished"),
+    };
+
+    let seats: Vec<String> = Player::seats(metadata.player_count())
+        .iter()
+        .map(|player| {
+            format!(
+                "{} is {}",
+                player.character(),
+                describe(metadata.participant(player))
+            )
+        })
+        .collect();
+    println!(
+        "{} ({}): {}, {} goes first",
+        metadata.date,
+        metadata
+            .definition
+            .as_deref()
+            .unwrap_or(metadata.variant.name()),
+        seats.join(", "),
+        metadata.first_player.character()
+    );
+    println!(
+        "{} after {} moves ({})",
+        result,
+        record.moves.len(),
+        metadata.termination.description()
+    );
+}
+
+fn sub_board_lines(board: &Board) -> [String; 3] {
+    match winning_player_on(board) {
+        Some(Player::X) => [
+            String::from(" \\   / "),
+            String::from("   X   "),
+            String::from(" /   \\ "),
+        ],
+        Some(player) => [
+            String::from(" ,---, "),
+            format!(" | {} | ", player.character()),
+            String::from(" '---' "),
+        ],
+        None => {
+            let line = |row_index: usize| {
+                let markers: Vec<&str> = board.rows[row_index]
+                    .cells
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Marker(player) => player.character(),
+                        Cell::Empty => ".",
+                        Cell::Symbol(_) | Cell::Blocked | Cell::Number(_) => cell.character(),
+                    })
+                    .collect();
+                format!(" {} ", markers.join(" "))
+            };
+            [line(0), line(1), line(2)]
+        }
+    }
+}
+
+pub fn render_meta_board(meta_board: &MetaBoard) {
+    println!("        1       2       3");
+    println!("    ,-------,-------,-------,");
+
+    for (board_row, row) in meta_board.boards.iter().enumerate() {
+        if board_row > 0 {
+            println!("    |-------+-------+-------|");
+        }
+
+        let [left, middle, right] = [
+            sub_board_lines(&row[0]),
+            sub_board_lines(&row[1]),
+            sub_board_lines(&row[2]),
+        ];
+        for (line_index, left_line) in left.iter().enumerate() {
+            let label = if line_index == 1 {
+                row_letter(board_row)
+            } else {
+                ' '
+            };
+            println!(
+                "  {} |{}|{}|{}|",
+                label, left_line, middle[line_index], right[line_index]
+            );
+        }
+    }
+
+    println!("    '-------'-------'-------'");
+}
+
+pub fn render_available_boards(meta_board: &MetaBoard) {
+    let available_boards = meta_board.available_boards();
+    match available_boards.as_slice() {
+        [] => {}
+        [(board_row, board_col)] => println!("Play in board {}", cell_name(*board_row, *board_col)),
+        _ => println!("Free move! Play in any open board"),
+    }
+}
+
+pub fn render_cube(cube: &Cube) {
+    let layer_width = cube.size * 2 + 4;
+    let titles: Vec<String> = (0..cube.size)
+        .map(|layer_index| {
+            format!(
+                "{:<width$}",
+                format!("   Layer {}", layer_index + 1),
+                width = layer_width
+            )
+        })
+        .collect();
+    let numbers: String = (1..=cube.size).map(|col| format!("{} ", col)).collect();
+
+    println!("{}", titles.join("  ").trim_end());
+    println!(
+        "{}",
+        vec![format!("   {:<width$}", numbers, width = layer_width - 3); cube.size]
+            .join("  ")
+            .trim_end()
+    );
+
+    for row_index in 0..cube.size {
+        let rows: Vec<String> = (0..cube.size)
+            .map(|layer_index| {
+                let markers: Vec<&str> = cube.layer_rows(layer_index)[row_index]
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Marker(player) => player.character(),
+                        Cell::Empty => ".",
+                        Cell::Symbol(_) | Cell::Blocked | Cell::Number(_) => cell.character(),
+                    })
+                    .collect();
+                format!(
+                    " {} {:<width$}",
+                    row_letter(row_index),
+                    markers.join(" "),
+                    width = layer_width - 3
+                )
+            })
+            .collect();
+        println!("{}", rows.join("  ").trim_end());
+    }
+}
+
+pub fn render_notakto(notakto: &Notakto) {
+    let titles: Vec<String> = (0..notakto.boards.len())
+        .map(|board_index| {
+            let state = if notakto.is_dead(board_index) {
+                " dead"
+            } else {
+                ""
+            };
+            format!("{:<12}", format!(" Board {}{}", board_index + 1, state))
+        })
+        .collect();
+    println!("{}", titles.join("  ").trim_end());
+    println!(
+        "{}",
+        vec!["   1 2 3    "; notakto.boards.len()]
+            .join("  ")
+            .trim_end()
+    );
+
+    for row_index in 0..3 {
+        let rows: Vec<String> = notakto
+            .boards
+            .iter()
+            .map(|board| {
+                let markers: Vec<&str> = board.rows[row_index]
+                    .cells
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Marker(player) => player.character(),
+                        Cell::Empty => ".",
+                        Cell::Symbol(_) | Cell::Blocked | Cell::Number(_) => cell.character(),
+                    })
+                    .collect();
+                format!(" {} {:<9}", row_letter(row_index), markers.join(" "))
+            })
+            .collect();
+        println!("{}", rows.join("  ").trim_end());
+    }
+}
+
+// Settled marks are shown in capitals, spooky ones in lowercase with their
+// move numbers, so "x1 o2" is a cell two marks are still entangled in.
+pub fn render_quantum(quantum: &Quantum) {
+    let contents: Vec<Vec<String>> = (0..3)
+        .map(|row_index| {
+            (0..3)
+                .map(
+                    |col_index| match quantum.classical_mark((row_index, col_index)) {
+                        Some(mark) => mark.label(),
+                        None => quantum
+                            .spooky_marks((row_index, col_index))
+                            .iter()
+                            .map(|mark| mark.label().to_lowercase())
+                            .collect::<Vec<String>>()
+                            .join(" "),
+                    },
+                )
+                .collect()
+        })
+        .collect();
+    let width = contents
+        .iter()
+        .flatten()
+        .map(|content| content.len())
+        .max()
+        .unwrap_or(0)
+        .max(3)
+        + 2;
+    let bar = "-".repeat(width);
+
+    println!("   ,{},{},{},", bar, bar, bar);
+    for (row_index, row) in contents.iter().enumerate() {
+        if row_index > 0 {
+            println!("   |{}+{}+{}|", bar, bar, bar);
+        }
+        let cells: Vec<String> = row
+            .iter()
+            .map(|content| format!("{:^width$}", content, width = width))
+            .collect();
+        println!(" {} |{}|", row_letter(row_index), cells.join("|"));
+    }
+    println!("   '{}'{}'{}'", bar, bar, bar);
+    println!(
+        "    {}",
+        (1..=3)
+            .map(|col| format!("{:^width$}", col, width = width + 1))
+            .collect::<String>()
+            .trim_end()
+    );
+}
@@ -0,0 +1,29 @@
+use crate::models::board::Board;
+use crate::models::player::Player;
+use crate::utils::lines::{line_weight, Lines};
+
+// Only one cell per column is ever playable, so the tree is narrow enough to
+// look several plies ahead without trimming candidates.
+pub const SEARCH_DEPTH: usize = 6;
+
+// A line holding both players' marks is dead and counts for nothing.
+pub fn evaluate(board: &Board, lines: &Lines, for_player: &Player) -> i32 {
+    (0..lines.cells.len())
+        .map(|line_index| {
+            let (mine, theirs) = lines.marks(board, line_index, for_player);
+            match (mine, theirs) {
+                (mine, 0) => line_weight(mine),
+                (0, theirs) => -line_weight(theirs),
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+// The middle columns come first since they take part in the most lines.
+pub fn drop_columns(board: &Board) -> Vec<usize> {
+    let col_count = board.size().cols;
+    let mut cols: Vec<usize> = (0..col_count).collect();
+    cols.sort_by_key(|&col_index| (2 * col_index).abs_diff(col_count.saturating_sub(1)));
+    cols
+}
@@ -0,0 +1,42 @@
+use crate::models::cell::Cell;
+use crate::models::infinite::Infinite;
+use crate::models::player::Player;
+use crate::utils::ai::win_arrangements;
+
+// The search carries the mark queues along with the board, so it sees marks
+// disappear just as the game does.
+pub const SEARCH_DEPTH: usize = 6;
+
+// Marks that will still be there after the player's next move. The fading
+// mark goes as soon as they place another, so it can't help them finish a
+// line.
+fn lasting_count(infinite: &Infinite, line: &[(usize, usize)], player: &Player) -> i32 {
+    let fading = infinite.fading_mark(player);
+    let marker = Cell::Marker(player.clone());
+    line.iter()
+        .filter(|&&(row_index, col_index)| {
+            infinite.board.rows[row_index].cells[col_index] == marker
+                && Some((row_index, col_index)) != fading
+        })
+        .count() as i32
+}
+
+pub fn evaluate(infinite: &Infinite, for_player: &Player) -> i32 {
+    let opponent = for_player.opponent();
+    win_arrangements(&infinite.board)
+        .iter()
+        .map(|cell_positions| {
+            let line: Vec<(usize, usize)> = cell_positions
+                .iter()
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect();
+            let mine = lasting_count(infinite, &line, for_player);
+            let theirs = lasting_count(infinite, &line, &opponent);
+            match (mine, theirs) {
+                (mine, 0) => mine * mine,
+                (0, theirs) => -theirs * theirs,
+                _ => 0,
+            }
+        })
+        .sum()
+}
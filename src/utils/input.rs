@@ -1,44 +1,118 @@
+use crate::error::{Error, InputError};
+use crate::models::board::BoardSize;
+use crate::models::cube::CubePosition;
+use crate::models::definition::VariantDefinition;
+use crate::models::gobblet::{GobbletMove, PieceSize};
+use crate::models::gomoku::{SwapChoice, BLACK, WHITE};
+use crate::models::meta_board::{BoardPosition, MetaMove};
+use crate::models::morris::MorrisMove;
+use crate::models::notakto::NotaktoMove;
+use crate::models::numerical::NumberedMove;
 use crate::models::player::Player;
+use crate::models::quantum::Position;
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::utils::definitions::find_definition;
 use regex::Regex;
-use std::io::{self, Write};
+use std::cell::{Cell, RefCell};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
 
 const YES_PATTERN: &str = r"(?i)^\s*(y+|y+e+s+|y+e+a+h+|y+e+p+|y+u+p+|y+e+|y+a+r+|m+h+m+|true|1)\s*(pls|please|thx|thanks|man|dude|dawg|bro|bruh)?\s*$";
 const NO_PATTERN: &str = r"(?i)^\s*(n+|n+o+|n+o+p+e+|n+a+h+|false|0)\s*(pls|please|thx|thanks|man|dude|dawg|bro|bruh)?\s*$";
 
-pub fn invalid_input(message: &str) -> io::Error {
-    io::Error::new(io::ErrorKind::InvalidInput, message)
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+thread_local! {
+    static SCRIPT: RefCell<Option<Box<dyn BufRead>>> = RefCell::new(None);
+    static MAX_RETRIES: Cell<usize> = const { Cell::new(DEFAULT_MAX_RETRIES) };
+}
+
+pub fn use_script(path: &str) -> Result<(), Error> {
+    let reader: Box<dyn BufRead> = Box::new(BufReader::new(File::open(path)?));
+    SCRIPT.with(|script| *script.borrow_mut() = Some(reader));
+    Ok(())
+}
+
+pub fn set_max_retries(max_retries: usize) {
+    MAX_RETRIES.with(|max| max.set(max_retries));
+}
+
+fn read_line(input: &mut String) -> Result<usize, Error> {
+    let scripted = SCRIPT.with(|script| {
+        script
+            .borrow_mut()
+            .as_mut()
+            .map(|reader| reader.read_line(input))
+    });
+
+    match scripted {
+        Some(result) => Ok(result?),
+        None => Ok(io::stdin().read_line(input)?),
+    }
+}
+
+fn interactive() -> bool {
+    SCRIPT.with(|script| script.borrow().is_none()) && io::stdin().is_terminal()
 }
 
-pub fn ask_for_string(prompt: &str) -> Result<String, io::Error> {
+pub fn ask_for_string(prompt: &str) -> Result<String, Error> {
     print!("{} ", prompt.trim());
-    io::stdout().flush().unwrap();
+    io::stdout().flush()?;
 
     let mut input = String::new();
-    match io::stdin().read_line(&mut input) {
-        Ok(_) => Ok(String::from(input.trim())),
-        Err(e) => Err(e),
+    if read_line(&mut input)? == 0 {
+        return Err(Error::Input(InputError::EndOfInput));
+    }
+
+    // Scripted answers don't show up on the terminal, so echo them to keep
+    // the transcript readable.
+    if !interactive() {
+        println!("{}", input.trim());
+    }
+
+    Ok(String::from(input.trim()))
+}
+
+// Re-asks after invalid input or an illegal move, up to the configured
+// number of retries. Running out of input or I/O failures end it right away.
+pub fn with_retries<T>(mut attempt: impl FnMut() -> Result<T, Error>) -> Result<T, Error> {
+    let max_retries = MAX_RETRIES.with(|max| max.get());
+
+    for _ in 0..max_retries {
+        match attempt() {
+            Err(Error::Input(error)) if error.is_retryable() => println!("{}. Try again!", error),
+            Err(Error::Move(error)) => println!("{}. Try again!", error),
+            result => return result,
+        }
     }
+
+    attempt().map_err(|error| match error {
+        Error::Input(ref input_error) if !input_error.is_retryable() => error,
+        Error::Input(_) | Error::Move(_) => Error::Input(InputError::TooManyAttempts),
+        _ => error,
+    })
 }
 
 #[allow(dead_code)]
-pub fn ask_for_number(prompt: &str) -> Result<isize, io::Error> {
+pub fn ask_for_number(prompt: &str) -> Result<isize, Error> {
     ask_for_string(prompt).and_then(|input| {
         input
             .parse::<isize>()
-            .map_err(|_| invalid_input("Not a number"))
+            .map_err(|_| Error::Input(InputError::NotANumber(input.clone())))
     })
 }
 
-pub fn ask_for_character(prompt: &str) -> Result<char, io::Error> {
+pub fn ask_for_character(prompt: &str) -> Result<char, Error> {
     ask_for_string(prompt).and_then(|input| {
         input
             .chars()
             .next()
-            .ok_or_else(|| invalid_input("Not a character"))
+            .ok_or(Error::Input(InputError::NotACharacter))
     })
 }
 
-pub fn ask_for_bool(prompt: &str, default: bool) -> Result<bool, io::Error> {
+pub fn ask_for_bool(prompt: &str, default: bool) -> Result<bool, Error> {
     let yes_regex = Regex::new(YES_PATTERN).unwrap();
     let no_regex = Regex::new(NO_PATTERN).unwrap();
 
@@ -53,67 +127,432 @@ pub fn ask_for_bool(prompt: &str, default: bool) -> Result<bool, io::Error> {
     })
 }
 
-pub fn ask_for_alpha_num(prompt: &str) -> Result<(String, usize), io::Error> {
+pub fn ask_for_alpha_num(prompt: &str) -> Result<(String, usize), Error> {
     let alpha_num_regex = Regex::new(r"^\s*([A-Za-z]+)\W*(\d+)\s*$").unwrap();
     ask_for_string(prompt).and_then(|input| {
         alpha_num_regex
             .captures(&input)
-            .ok_or_else(|| invalid_input("Not a '<letter><number>'"))
-            .map(|captures| {
+            .ok_or_else(|| Error::Input(InputError::NotAlphaNum(input.clone())))
+            .and_then(|captures| {
                 let alpha = String::from(&captures[1]);
-                let num = captures[2].parse::<usize>().unwrap();
-                (alpha, num)
+                let num = captures[2]
+                    .parse::<usize>()
+                    .map_err(|_| InputError::NotANumber(String::from(&captures[2])))?;
+                Ok((alpha, num))
             })
     })
 }
 
-pub fn ask_for_cell_position(prompt: &str) -> (usize, usize) {
-    if let Ok((alpha, num)) = ask_for_alpha_num(prompt) {
-        if num == 0 || num > 3 {
-            println!("{} is not a column. Try again!", num);
-            return ask_for_cell_position(prompt);
+pub fn parse_cell_position(alpha: &str, num: usize) -> Result<(usize, usize), InputError> {
+    parse_board_position(alpha, num, BoardSize::CLASSIC)
+}
+
+// Rows are lettered from A and columns numbered from 1, as far as the board
+// goes.
+pub fn parse_board_position(
+    alpha: &str,
+    num: usize,
+    size: BoardSize,
+) -> Result<(usize, usize), InputError> {
+    if num == 0 || num > size.cols {
+        return Err(InputError::NotAColumn(num));
+    }
+
+    let row_index = match alpha.to_ascii_uppercase().as_bytes() {
+        [letter @ b'A'..=b'Z'] if ((letter - b'A') as usize) < size.rows => {
+            (letter - b'A') as usize
         }
+        _ => return Err(InputError::NotARow(String::from(alpha))),
+    };
+
+    Ok((row_index, num - 1))
+}
+
+pub fn ask_for_cell_position(prompt: &str) -> Result<(usize, usize), Error> {
+    with_retries(|| {
+        let (alpha, num) = ask_for_alpha_num(prompt)?;
+        Ok(parse_cell_position(&alpha, num)?)
+    })
+}
 
-        let col_index = num - 1;
+pub fn ask_for_board_position(prompt: &str, size: BoardSize) -> Result<(usize, usize), Error> {
+    with_retries(|| {
+        let (alpha, num) = ask_for_alpha_num(prompt)?;
+        Ok(parse_board_position(&alpha, num, size)?)
+    })
+}
 
-        let row_index = match alpha.as_str() {
-            "A" | "a" => 0,
-            "B" | "b" => 1,
-            "C" | "c" => 2,
-            _ => 99,
+// Several cells on one line, like 'J10 K11' or 'J10, K11', for turns that
+// place more than one mark.
+pub fn ask_for_board_positions(
+    prompt: &str,
+    size: BoardSize,
+    count: usize,
+) -> Result<Vec<(usize, usize)>, Error> {
+    let cell_regex = Regex::new(r"^([A-Za-z]+)(\d+)$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let not_cells = || InputError::NotCells(input.clone(), count);
+        let tokens: Vec<&str> = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect();
+        if tokens.len() != count {
+            return Err(Error::Input(not_cells()));
+        }
+
+        tokens
+            .iter()
+            .map(|token| {
+                let captures = cell_regex.captures(token).ok_or_else(not_cells)?;
+                Ok(parse_board_position(
+                    &captures[1],
+                    parse_number(&captures[2])?,
+                    size,
+                )?)
+            })
+            .collect()
+    })
+}
+
+pub fn ask_for_wild_move(prompt: &str, size: BoardSize) -> Result<(Symbol, usize, usize), Error> {
+    let wild_move_regex = Regex::new(r"^\s*([XxOo])\W*([A-Za-z]+)\W*(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = wild_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotAWildMove(input.clone()))?;
+        let symbol = match &captures[1] {
+            "X" | "x" => Symbol::X,
+            _ => Symbol::O,
         };
+        let (row_index, col_index) =
+            parse_board_position(&captures[2], parse_number(&captures[3])?, size)?;
+        Ok((symbol, row_index, col_index))
+    })
+}
+
+pub fn ask_for_numbered_move(prompt: &str) -> Result<NumberedMove, Error> {
+    let numbered_move_regex = Regex::new(r"^\s*(\d)\W*([A-Za-z]+)\W*?(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = numbered_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotANumberedMove(input.clone()))?;
+        let number = parse_number(&captures[1])? as u8;
+        let (row_index, col_index) =
+            parse_cell_position(&captures[2], parse_number(&captures[3])?)?;
+        Ok((number, row_index, col_index))
+    })
+}
 
-        if row_index == 99 {
-            println!("{} is not a row. Try again!", alpha);
-            return ask_for_cell_position(prompt);
+pub fn ask_for_notakto_move(prompt: &str, board_count: usize) -> Result<NotaktoMove, Error> {
+    let notakto_move_regex = Regex::new(r"^\s*(\d+)\W*([A-Za-z]+)\W*(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = notakto_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotABoardCell(input.clone()))?;
+        let board = parse_number(&captures[1])?;
+        if board == 0 || board > board_count {
+            return Err(Error::Input(InputError::NotABoard(board)));
         }
+        let (row_index, col_index) =
+            parse_cell_position(&captures[2], parse_number(&captures[3])?)?;
+        Ok((board - 1, row_index, col_index))
+    })
+}
 
-        (row_index, col_index)
-    } else {
-        println!("That's not a valid position! Try again.");
-        ask_for_cell_position(prompt)
-    }
+pub fn ask_for_morris_move(prompt: &str) -> Result<MorrisMove, Error> {
+    let morris_move_regex =
+        Regex::new(r"^\s*([A-Za-z]+)\W*?(\d+)(?:\s*-\s*([A-Za-z]+)\W*?(\d+))?\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = morris_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotAMorrisMove(input.clone()))?;
+        let first = parse_cell_position(&captures[1], parse_number(&captures[2])?)?;
+
+        match (captures.get(3), captures.get(4)) {
+            (Some(alpha), Some(num)) => {
+                let to = parse_cell_position(alpha.as_str(), parse_number(num.as_str())?)?;
+                Ok(MorrisMove::Slide { from: first, to })
+            }
+            _ => Ok(MorrisMove::Place(first)),
+        }
+    })
 }
 
-pub fn ask_for_player_character(prompt: &str) -> Player {
-    match ask_for_character(prompt) {
-        Ok('X') | Ok('x') => Player::X,
-        Ok('O') | Ok('o') => Player::O,
-        _ => {
-            println!("That's not a valid choice! Try again.");
-            ask_for_player_character(prompt)
+pub fn ask_for_gobblet_move(prompt: &str) -> Result<GobbletMove, Error> {
+    let place_regex = Regex::new(r"^\s*([SMLsml])\W*([A-Za-z])\W*?(\d+)\s*$").unwrap();
+    let move_regex =
+        Regex::new(r"^\s*([A-Za-z]+)\W*?(\d+)\s*-\s*([A-Za-z]+)\W*?(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        if let Some(captures) = place_regex.captures(&input) {
+            let size = captures[1]
+                .chars()
+                .next()
+                .and_then(PieceSize::from_letter)
+                .ok_or_else(|| InputError::NotAGobbletMove(input.clone()))?;
+            let to = parse_cell_position(&captures[2], parse_number(&captures[3])?)?;
+            return Ok(GobbletMove::Place { size, to });
         }
+
+        let captures = move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotAGobbletMove(input.clone()))?;
+        let from = parse_cell_position(&captures[1], parse_number(&captures[2])?)?;
+        let to = parse_cell_position(&captures[3], parse_number(&captures[4])?)?;
+        Ok(GobbletMove::Move { from, to })
+    })
+}
+
+pub fn ask_for_spooky_move(prompt: &str) -> Result<(Position, Position), Error> {
+    let spooky_move_regex =
+        Regex::new(r"^\s*([A-Za-z]+)\W*?(\d+)\W+([A-Za-z]+)\W*?(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = spooky_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotASpookyMove(input.clone()))?;
+        let first = parse_cell_position(&captures[1], parse_number(&captures[2])?)?;
+        let second = parse_cell_position(&captures[3], parse_number(&captures[4])?)?;
+        Ok((first, second))
+    })
+}
+
+pub fn ask_for_column(prompt: &str, col_count: usize) -> Result<usize, Error> {
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let col = parse_number(input.trim())?;
+        if col == 0 || col > col_count {
+            return Err(Error::Input(InputError::NotAColumn(col)));
+        }
+        Ok(col - 1)
+    })
+}
+
+pub fn ask_for_cube_position(prompt: &str, size: usize) -> Result<CubePosition, Error> {
+    let cube_position_regex = Regex::new(r"^\s*(\d+)\W*([A-Za-z]+)\W*(\d+)\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = cube_position_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotACubeCell(input.clone()))?;
+        let layer = parse_number(&captures[1])?;
+        let col = parse_number(&captures[3])?;
+        let row_letter = captures[2].to_ascii_uppercase();
+
+        if layer == 0 || layer > size {
+            return Err(Error::Input(InputError::NotALayer(layer)));
+        }
+        if col == 0 || col > size {
+            return Err(Error::Input(InputError::NotAColumn(col)));
+        }
+        let row_index = match row_letter.as_bytes() {
+            [letter] if ((letter - b'A') as usize) < size => (letter - b'A') as usize,
+            _ => {
+                return Err(Error::Input(InputError::NotARow(String::from(
+                    &captures[2],
+                ))))
+            }
+        };
+
+        Ok((layer - 1, row_index, col - 1))
+    })
+}
+
+pub fn ask_for_meta_move(
+    prompt: &str,
+    forced_board: Option<BoardPosition>,
+) -> Result<MetaMove, Error> {
+    let meta_move_regex =
+        Regex::new(r"^\s*([A-Za-z]+)\W*(\d+)(?:\W+([A-Za-z]+)\W*(\d+))?\s*$").unwrap();
+
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        let captures = meta_move_regex
+            .captures(&input)
+            .ok_or_else(|| InputError::NotAlphaNum(input.clone()))?;
+        let first = parse_cell_position(&captures[1], parse_number(&captures[2])?)?;
+
+        match (captures.get(3), captures.get(4), forced_board) {
+            (Some(alpha), Some(num), _) => {
+                let second = parse_cell_position(alpha.as_str(), parse_number(num.as_str())?)?;
+                Ok((first, second))
+            }
+            (_, _, Some(board_position)) => Ok((board_position, first)),
+            _ => Err(Error::Input(InputError::MissingBoard)),
+        }
+    })
+}
+
+fn parse_number(input: &str) -> Result<usize, InputError> {
+    input
+        .parse::<usize>()
+        .map_err(|_| InputError::NotANumber(String::from(input)))
+}
+
+pub fn ask_for_player_character(prompt: &str) -> Result<Player, Error> {
+    with_retries(|| match ask_for_character(prompt)? {
+        'X' | 'x' => Ok(Player::X),
+        'O' | 'o' => Ok(Player::O),
+        character => Err(Error::Input(InputError::NotAPlayer(character))),
+    })
+}
+
+pub fn ask_for_seat(prompt: &str, player_count: usize) -> Result<Player, Error> {
+    with_retries(|| {
+        let character = ask_for_character(prompt)?;
+        Player::from_character(character)
+            .filter(|player| player.index() < player_count)
+            .ok_or(Error::Input(InputError::NotASeat(character)))
+    })
+}
+
+pub fn ask_for_swap_choice(prompt: &str, can_add_two: bool) -> Result<SwapChoice, Error> {
+    with_retries(|| {
+        let input = ask_for_string(prompt)?;
+        match input.to_lowercase().as_str() {
+            "b" | "black" => Ok(SwapChoice::Take(BLACK)),
+            "w" | "white" => Ok(SwapChoice::Take(WHITE)),
+            "m" | "more" if can_add_two => Ok(SwapChoice::AddTwo),
+            _ => Err(Error::Input(InputError::NotASwapChoice(input))),
+        }
+    })
+}
+
+// The built-in variants are listed first, then the ones from variant files,
+// which come back with their definition.
+pub fn ask_for_variant(
+    prompt: &str,
+    definitions: &[VariantDefinition],
+) -> Result<(Variant, Option<VariantDefinition>), Error> {
+    for (index, variant) in Variant::ALL.iter().enumerate() {
+        println!("  {}. {}", index + 1, variant.description());
     }
+    for (index, definition) in definitions.iter().enumerate() {
+        println!(
+            "  {}. {}",
+            Variant::ALL.len() + index + 1,
+            definition.description()
+        );
+    }
+    let choice_count = Variant::ALL.len() + definitions.len();
+    let custom = |definition: &VariantDefinition| (Variant::Custom, Some(definition.clone()));
+
+    with_retries(|| {
+        let input = ask_for_string(&format!("{} (1-{})", prompt.trim(), choice_count))?;
+        if input.is_empty() {
+            return Ok((Variant::default(), None));
+        }
+
+        let chosen = match input.parse::<usize>() {
+            Ok(choice) if choice > Variant::ALL.len() => {
+                definitions.get(choice - Variant::ALL.len() - 1).map(custom)
+            }
+            Ok(choice) => Variant::ALL
+                .get(choice.wrapping_sub(1))
+                .map(|variant| (*variant, None)),
+            Err(_) => Variant::from_name(&input)
+                .map(|variant| (variant, None))
+                .or_else(|| find_definition(definitions, &input).map(custom)),
+        };
+        chosen.ok_or(Error::Input(InputError::NotAVariant(input)))
+    })
 }
 
-pub fn confirm(prompt: &str, default: bool) -> bool {
+pub fn confirm(prompt: &str, default: bool) -> Result<bool, Error> {
     let choices = if default { "Y/n" } else { "y/N" };
     let prompt_with_yes_no = format!("{} ({})", prompt.trim(), choices);
-    match ask_for_bool(prompt_with_yes_no.as_str(), true) {
-        Ok(answer) => answer,
-        _ => {
-            println!("Valid choices are, like, 'yes' and 'no'. Get it? All right, try again.");
-            confirm(prompt, default)
-        }
+    ask_for_bool(prompt_with_yes_no.as_str(), default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MoveError;
+
+    #[test]
+    fn retries_stop_after_the_configured_number() {
+        set_max_retries(2);
+        let mut attempts = 0;
+        let result: Result<(), Error> = with_retries(|| {
+            attempts += 1;
+            Err(Error::Move(MoveError::GameOver))
+        });
+
+        assert_eq!(attempts, 3);
+        assert!(matches!(
+            result,
+            Err(Error::Input(InputError::TooManyAttempts))
+        ));
+    }
+
+    #[test]
+    fn a_good_answer_after_a_bad_one_is_taken() {
+        set_max_retries(DEFAULT_MAX_RETRIES);
+        let mut attempts = 0;
+        let result = with_retries(|| {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::Input(InputError::NotANumber(String::from("x")))),
+                _ => Ok(attempts),
+            }
+        });
+
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn running_out_of_input_ends_it_right_away() {
+        set_max_retries(DEFAULT_MAX_RETRIES);
+        let mut attempts = 0;
+        let result: Result<(), Error> = with_retries(|| {
+            attempts += 1;
+            Err(Error::Input(InputError::EndOfInput))
+        });
+
+        assert_eq!(attempts, 1);
+        assert!(result.unwrap_err().is_end_of_input());
+    }
+
+    #[test]
+    fn running_out_of_input_on_the_last_retry_is_still_end_of_input() {
+        set_max_retries(1);
+        let mut attempts = 0;
+        let result: Result<(), Error> = with_retries(|| {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::Input(InputError::NotACharacter)),
+                _ => Err(Error::Input(InputError::EndOfInput)),
+            }
+        });
+
+        assert_eq!(attempts, 2);
+        assert!(result.unwrap_err().is_end_of_input());
+    }
+
+    #[test]
+    fn a_script_that_runs_out_ends_the_input() {
+        let path = std::env::temp_dir().join(format!(
+            "tic-tac-toe-rust-script-test-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "B2\n").unwrap();
+        use_script(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(ask_for_string("Which cell?").unwrap(), "B2");
+        assert!(ask_for_string("Which cell?").unwrap_err().is_end_of_input());
+        std::fs::remove_file(path).unwrap();
     }
 }
@@ -0,0 +1,241 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+type Position = (usize, usize);
+
+// A board's winning lines, flattened once so the AIs don't have to rebuild
+// them at every node, along with which lines run through each cell.
+#[derive(Debug)]
+pub struct Lines {
+    col_count: usize,
+    pub cells: Vec<Vec<Position>>,
+    through: Vec<Vec<usize>>,
+}
+
+impl Lines {
+    pub fn of(board: &Board) -> Self {
+        let size = board.size();
+        let cells: Vec<Vec<Position>> = board
+            .get_cell_position_lines()
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|&(_, row_index, col_index)| (row_index, col_index))
+                    .collect()
+            })
+            .collect();
+
+        let mut through = vec![vec![]; size.rows * size.cols];
+        for (line_index, line) in cells.iter().enumerate() {
+            for &(row_index, col_index) in line {
+                through[row_index * size.cols + col_index].push(line_index);
+            }
+        }
+
+        Self {
+            col_count: size.cols,
+            cells,
+            through,
+        }
+    }
+
+    // The indexes of the lines the cell is on.
+    pub fn through(&self, position: Position) -> &[usize] {
+        &self.through[position.0 * self.col_count + position.1]
+    }
+
+    // How many of the line's cells hold the player's marks, and how many
+    // hold anyone else's.
+    pub fn marks(&self, board: &Board, line_index: usize, player: &Player) -> (usize, usize) {
+        self.count(board, line_index, &Cell::Marker(player.clone()))
+    }
+
+    // The same for symbols: how many are the given one, and how many the
+    // other.
+    pub fn symbols(&self, board: &Board, line_index: usize, symbol: Symbol) -> (usize, usize) {
+        self.count(board, line_index, &Cell::Symbol(symbol))
+    }
+
+    fn count(&self, board: &Board, line_index: usize, mark: &Cell) -> (usize, usize) {
+        self.cells[line_index]
+            .iter()
+            .fold(
+                (0, 0),
+                |(same, other), &(row_index, col_index)| match &board.rows[row_index].cells
+                    [col_index]
+                {
+                    cell if cell == mark => (same + 1, other),
+                    Cell::Marker(_) | Cell::Symbol(_) => (same, other + 1),
+                    Cell::Empty | Cell::Blocked | Cell::Number(_) => (same, other),
+                },
+            )
+    }
+
+    pub fn empty_cells(&self, board: &Board, line_index: usize) -> Vec<Position> {
+        self.cells[line_index]
+            .iter()
+            .filter(|&&(row_index, col_index)| {
+                board.rows[row_index].cells[col_index] == Cell::Empty
+            })
+            .copied()
+            .collect()
+    }
+
+    // Whether a mark of the player's on the cell would finish one of its
+    // lines.
+    pub fn completed_by(&self, board: &Board, position: Position, player: &Player) -> bool {
+        let marker = Cell::Marker(player.clone());
+        self.through(position).iter().any(|&line_index| {
+            self.cells[line_index]
+                .iter()
+                .all(|&(row, col)| (row, col) == position || board.rows[row].cells[col] == marker)
+        })
+    }
+}
+
+// An open line is worth more the fuller it is, eight times as much for every
+// mark.
+pub fn line_weight(count: usize) -> i32 {
+    match count {
+        0 => 0,
+        count => 8i32.saturating_pow(count as u32 - 1),
+    }
+}
+
+// Every direction a line can run in a grid with this many dimensions: along an
+// axis, along a face diagonal, or through a space diagonal. Only one of each
+// direction/opposite pair is kept, ordered axes first so that 2D boards list
+// rows, then columns, then the two diagonals.
+pub fn directions(dimension_count: usize) -> Vec<Vec<isize>> {
+    let mut directions: Vec<Vec<isize>> = (0..3usize.pow(dimension_count as u32))
+        .map(|index| {
+            (0..dimension_count)
+                .rev()
+                .map(|axis| (index / 3usize.pow(axis as u32) % 3) as isize - 1)
+                .collect::<Vec<isize>>()
+        })
+        .filter(|direction| direction.iter().find(|&&step| step != 0) == Some(&1))
+        .collect();
+
+    directions.sort_by_key(|direction| {
+        let nonzero_count = direction.iter().filter(|&&step| step != 0).count();
+        let reversed: Vec<isize> = direction.iter().rev().copied().collect();
+        (nonzero_count, Reverse(reversed))
+    });
+    directions
+}
+
+fn coordinates_of(index: usize, dimensions: &[usize]) -> Vec<usize> {
+    let mut remainder = index;
+    let mut coordinates = vec![0; dimensions.len()];
+    for (axis, size) in dimensions.iter().enumerate().rev() {
+        coordinates[axis] = remainder % size;
+        remainder /= size;
+    }
+    coordinates
+}
+
+// All runs of `length` cells in a straight line, as coordinates. A run is
+// listed once, starting from the end its direction points away from.
+pub fn lines(dimensions: &[usize], length: usize) -> Vec<Vec<Vec<usize>>> {
+    let cell_count: usize = dimensions.iter().product();
+
+    directions(dimensions.len())
+        .iter()
+        .flat_map(|direction| {
+            (0..cell_count).filter_map(move |index| {
+                let start = coordinates_of(index, dimensions);
+                (0..length)
+                    .map(|step| {
+                        start
+                            .iter()
+                            .zip(direction)
+                            .zip(dimensions)
+                            .map(|((&coordinate, &delta), &size)| {
+                                let moved = coordinate as isize + delta * step as isize;
+                                if moved >= 0 && (moved as usize) < size {
+                                    Some(moved as usize)
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect::<Option<Vec<usize>>>()
+                    })
+                    .collect::<Option<Vec<Vec<usize>>>>()
+            })
+        })
+        .collect()
+}
+
+// The same runs on a torus, where a line leaving one edge comes back in at the
+// opposite one. Every cell starts a run in every direction; a run that goes
+// all the way round and meets itself is dropped, and a run reachable from
+// more than one start is only listed once.
+pub fn wrapped_lines(dimensions: &[usize], length: usize) -> Vec<Vec<Vec<usize>>> {
+    let cell_count: usize = dimensions.iter().product();
+    let mut seen = HashSet::new();
+
+    directions(dimensions.len())
+        .iter()
+        .flat_map(|direction| {
+            (0..cell_count).map(move |index| {
+                let start = coordinates_of(index, dimensions);
+                (0..length)
+                    .map(|step| {
+                        start
+                            .iter()
+                            .zip(direction)
+                            .zip(dimensions)
+                            .map(|((&coordinate, &delta), &size)| {
+                                (coordinate as isize + delta * step as isize)
+                                    .rem_euclid(size as isize)
+                                    as usize
+                            })
+                            .collect::<Vec<usize>>()
+                    })
+                    .collect::<Vec<Vec<usize>>>()
+            })
+        })
+        .filter(|line| {
+            let mut cells = line.clone();
+            cells.sort();
+            cells.dedup();
+            cells.len() == line.len() && seen.insert(cells)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::board::BoardSize;
+    use crate::utils::ai::winning_player_on;
+
+    #[test]
+    fn a_torus_lists_each_wrapped_line_once() {
+        // Four rows, four columns and four of each diagonal, where the flat
+        // board only has its two main diagonals.
+        assert_eq!(lines(&[4, 4], 4).len(), 10);
+        assert_eq!(wrapped_lines(&[4, 4], 4).len(), 16);
+    }
+
+    #[test]
+    fn a_row_wraps_from_the_last_column_to_the_first() {
+        let mut board = Board::with_size(BoardSize {
+            rows: 4,
+            cols: 4,
+            win_length: 3,
+        });
+        for col_index in [3, 0, 1] {
+            board.rows[0].cells[col_index] = Cell::Marker(Player::X);
+        }
+        assert_eq!(winning_player_on(&board), None);
+
+        board.toroidal = true;
+        assert_eq!(winning_player_on(&board), Some(Player::X));
+    }
+}
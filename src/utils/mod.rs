@@ -1,3 +1,23 @@
 pub mod ai;
+pub mod connect6_ai;
+pub mod definitions;
+pub mod export;
+pub mod gobblet_ai;
+pub mod gomoku_ai;
 pub mod graphics;
+pub mod gravity_ai;
+pub mod infinite_ai;
 pub mod input;
+pub mod lines;
+pub mod multiplayer_ai;
+pub mod notakto_ai;
+pub mod notation;
+pub mod numerical_ai;
+pub mod order_chaos_ai;
+pub mod phantom_ai;
+pub mod quantum_ai;
+pub mod qubic_ai;
+pub mod rules_ai;
+pub mod ultimate_ai;
+pub mod validation;
+pub mod wild_ai;
@@ -0,0 +1,201 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::multiplayer::Multiplayer;
+use crate::models::player::Player;
+use crate::utils::lines::{line_weight, Lines};
+
+const WIN_SCORE: i32 = 1_000_000;
+const SEARCH_DEPTH: usize = 3;
+
+// A line only one player has marked is a threat from them; once two players
+// share it nobody can finish it.
+fn evaluate(board: &Board, lines: &Lines, for_player: &Player) -> i32 {
+    lines
+        .cells
+        .iter()
+        .map(|line| {
+            let mut owner: Option<&Player> = None;
+            let mut count = 0;
+            for &(row_index, col_index) in line {
+                if let Cell::Marker(player) = &board.rows[row_index].cells[col_index] {
+                    match owner {
+                        Some(owner) if owner != player => return 0,
+                        _ => owner = Some(player),
+                    }
+                    count += 1;
+                }
+            }
+            match owner {
+                Some(owner) if owner == for_player => line_weight(count),
+                Some(_) => -line_weight(count),
+                None => 0,
+            }
+        })
+        .sum()
+}
+
+// Cells on the most lines first, so the pruning bites sooner.
+fn empty_positions(board: &Board, lines: &Lines) -> Vec<(usize, usize)> {
+    let mut positions: Vec<(usize, usize)> = board
+        .get_cell_positions()
+        .iter()
+        .filter(|(cell, _, _)| **cell == Cell::Empty)
+        .map(|&(_, row_index, col_index)| (row_index, col_index))
+        .collect();
+    positions.sort_by_key(|&position| std::cmp::Reverse(lines.through(position).len()));
+    positions
+}
+
+// A paranoid search: everyone else is assumed to be playing against us
+// together, which turns the game back into two sides and lets alpha-beta
+// pruning work as usual.
+struct Paranoid<'a> {
+    lines: &'a Lines,
+    for_player: &'a Player,
+    player_count: usize,
+}
+
+impl Paranoid<'_> {
+    fn search(
+        &self,
+        board: &mut Board,
+        to_move: &Player,
+        depth: usize,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        let positions = empty_positions(board, self.lines);
+        if positions.is_empty() {
+            return 0;
+        }
+
+        let maximizing = to_move == self.for_player;
+        if positions
+            .iter()
+            .any(|&position| self.lines.completed_by(board, position, to_move))
+        {
+            let score = WIN_SCORE + depth as i32;
+            return if maximizing { score } else { -score };
+        }
+
+        if depth == 0 {
+            return evaluate(board, self.lines, self.for_player);
+        }
+
+        let (mut alpha, mut beta) = (alpha, beta);
+        for (row_index, col_index) in positions {
+            board.rows[row_index].cells[col_index] = Cell::Marker(to_move.clone());
+            let next = to_move.next(self.player_count);
+            let score = self.search(board, &next, depth - 1, alpha, beta);
+            board.rows[row_index].cells[col_index] = Cell::Empty;
+
+            if maximizing {
+                alpha = alpha.max(score);
+            } else {
+                beta = beta.min(score);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if maximizing {
+            alpha
+        } else {
+            beta
+        }
+    }
+}
+
+pub fn best_multiplayer_move(
+    multiplayer: &Multiplayer,
+    for_player: &Player,
+) -> Option<(usize, usize)> {
+    let mut scratch = multiplayer.board.clone();
+    let lines = Lines::of(&scratch);
+    let positions = empty_positions(&scratch, &lines);
+
+    if let Some(&position) = positions
+        .iter()
+        .find(|&&position| lines.completed_by(&scratch, position, for_player))
+    {
+        return Some(position);
+    }
+
+    let search = Paranoid {
+        lines: &lines,
+        for_player,
+        player_count: multiplayer.player_count,
+    };
+    let next = for_player.next(multiplayer.player_count);
+    let mut best: Option<((usize, usize), i32)> = None;
+    for (row_index, col_index) in positions {
+        scratch.rows[row_index].cells[col_index] = Cell::Marker(for_player.clone());
+        let best_score = best.map_or(-WIN_SCORE * 2, |(_, score)| score);
+        let score = search.search(
+            &mut scratch,
+            &next,
+            SEARCH_DEPTH - 1,
+            best_score,
+            WIN_SCORE * 2,
+        );
+        scratch.rows[row_index].cells[col_index] = Cell::Empty;
+
+        if best.is_none() || score > best_score {
+            best = Some(((row_index, col_index), score));
+        }
+    }
+
+    best.map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::board::BoardSize;
+    use crate::models::variant::Variant;
+
+    fn three_seats(marks: &[(Player, (usize, usize))]) -> Multiplayer {
+        let mut board = Board::with_size(BoardSize::MULTIPLAYER);
+        for (player, (row_index, col_index)) in marks {
+            board.rows[*row_index].cells[*col_index] = Cell::Marker(player.clone());
+        }
+        Multiplayer::on_board(Variant::Multiplayer, board, 3, &Player::X)
+    }
+
+    #[test]
+    fn the_next_player_is_blocked() {
+        let multiplayer = three_seats(&[
+            (Player::X, (2, 0)),
+            (Player::X, (4, 4)),
+            (Player::O, (0, 0)),
+            (Player::O, (0, 1)),
+            (Player::O, (0, 2)),
+            (Player::Y, (3, 4)),
+            (Player::Y, (2, 2)),
+        ]);
+        assert_eq!(
+            best_multiplayer_move(&multiplayer, &Player::X),
+            Some((0, 3))
+        );
+    }
+
+    #[test]
+    fn the_player_after_next_is_blocked_too() {
+        // O won't block Y for us, since the search assumes they're both out
+        // to get X.
+        let multiplayer = three_seats(&[
+            (Player::X, (0, 0)),
+            (Player::X, (2, 4)),
+            (Player::O, (1, 1)),
+            (Player::O, (0, 4)),
+            (Player::Y, (4, 0)),
+            (Player::Y, (4, 1)),
+            (Player::Y, (4, 2)),
+        ]);
+        assert_eq!(
+            best_multiplayer_move(&multiplayer, &Player::X),
+            Some((4, 3))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::notakto::{Notakto, NotaktoMove};
+
+// The eight ways to turn or flip a 3x3 board, as where cell `index` ends up.
+const SYMMETRIES: [[usize; 9]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8],
+    [6, 3, 0, 7, 4, 1, 8, 5, 2],
+    [8, 7, 6, 5, 4, 3, 2, 1, 0],
+    [2, 5, 8, 1, 4, 7, 0, 3, 6],
+    [2, 1, 0, 5, 4, 3, 8, 7, 6],
+    [6, 7, 8, 3, 4, 5, 0, 1, 2],
+    [0, 3, 6, 1, 4, 7, 2, 5, 8],
+    [8, 5, 2, 7, 4, 1, 6, 3, 0],
+];
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+fn board_mask(board: &Board) -> u16 {
+    board
+        .get_cells()
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| ***cell != Cell::Empty)
+        .fold(0, |mask, (index, _)| mask | 1 << index)
+}
+
+fn is_dead(mask: u16) -> bool {
+    LINES
+        .iter()
+        .any(|line| line.iter().all(|&index| mask & 1 << index != 0))
+}
+
+fn canonical(mask: u16) -> u16 {
+    SYMMETRIES
+        .iter()
+        .map(|symmetry| {
+            (0..9)
+                .filter(|&index| mask & 1 << index != 0)
+                .fold(0, |turned, index| turned | 1 << symmetry[index])
+        })
+        .min()
+        .unwrap_or(mask)
+}
+
+// A class in Notakto's misère quotient, as worked out by Plambeck and
+// Whitehead: the commutative monoid generated by a, b, c and d with
+//
+//   a² = 1, b³ = b, b²c = c, c³ = ac², b²d = d, cd = ad, d² = c²
+//
+// which has 18 elements. Every board has a class, a position's class is the
+// product of its boards' classes, and the player to move loses exactly when
+// that product is a, b², bc or c². Classes are kept as the powers of a, b, c
+// and d, reduced so that equal classes look the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Class([u8; 4]);
+
+const ONE: Class = Class([0, 0, 0, 0]);
+const A: Class = Class([1, 0, 0, 0]);
+const B: Class = Class([0, 1, 0, 0]);
+const AB: Class = Class([1, 1, 0, 0]);
+const C: Class = Class([0, 0, 1, 0]);
+const CC: Class = Class([0, 0, 2, 0]);
+const D: Class = Class([0, 0, 0, 1]);
+const AD: Class = Class([1, 0, 0, 1]);
+const LOSING: [Class; 4] = [A, Class([0, 2, 0, 0]), Class([0, 1, 1, 0]), CC];
+
+// The class of each live board, one per shape up to turning and flipping,
+// keyed by its canonical mask (bit `row * 3 + col` set for a marked cell,
+// so A1 is the last digit). Dead boards are out of play and count as 1. The
+// tests check these against an exhaustive search.
+const CLASSES: [(u16, Class); 46] = [
+    (0b000_000_000, C),
+    (0b000_000_001, ONE),
+    (0b000_000_010, ONE),
+    (0b000_000_011, D),
+    (0b000_000_101, B),
+    (0b000_001_010, A),
+    (0b000_001_011, B),
+    (0b000_001_100, B),
+    (0b000_001_101, A),
+    (0b000_001_110, AD),
+    (0b000_010_000, CC),
+    (0b000_010_001, B),
+    (0b000_010_010, B),
+    (0b000_010_011, AB),
+    (0b000_010_101, A),
+    (0b000_011_010, AB),
+    (0b000_011_011, A),
+    (0b000_011_100, A),
+    (0b000_011_101, B),
+    (0b000_011_110, B),
+    (0b000_101_000, A),
+    (0b000_101_001, AD),
+    (0b000_101_010, B),
+    (0b000_101_011, A),
+    (0b000_101_101, B),
+    (0b001_000_100, A),
+    (0b001_000_101, AB),
+    (0b001_000_110, AD),
+    (0b001_001_110, AB),
+    (0b001_100_001, A),
+    (0b001_100_010, ONE),
+    (0b001_100_011, B),
+    (0b001_100_101, B),
+    (0b001_100_110, A),
+    (0b001_101_010, AB),
+    (0b001_101_100, A),
+    (0b001_101_110, B),
+    (0b001_110_001, B),
+    (0b001_110_010, B),
+    (0b001_110_011, A),
+    (0b010_101_010, A),
+    (0b010_101_011, B),
+    (0b010_101_101, A),
+    (0b011_100_101, A),
+    (0b011_101_110, A),
+    (0b101_000_101, A),
+];
+
+impl Class {
+    fn times(self, other: Class) -> Class {
+        let Class([mut a, mut b, mut c, mut d]) = self;
+        a += other.0[0];
+        b += other.0[1];
+        c += other.0[2];
+        d += other.0[3];
+
+        loop {
+            if d >= 2 {
+                d -= 2;
+                c += 2;
+            } else if c >= 3 || (c >= 1 && d >= 1) {
+                c -= 1;
+                a += 1;
+            } else if b >= 3 {
+                b -= 2;
+            } else if b == 2 && (c >= 1 || d >= 1) {
+                b = 0;
+            } else {
+                return Class([a % 2, b, c, d]);
+            }
+        }
+    }
+
+    fn is_losing(self) -> bool {
+        LOSING.contains(&self)
+    }
+}
+
+fn class_of(mask: u16) -> Class {
+    if is_dead(mask) {
+        return ONE;
+    }
+
+    let shape = canonical(mask);
+    CLASSES
+        .iter()
+        .find(|&&(canonical_mask, _)| canonical_mask == shape)
+        .map_or(ONE, |&(_, class)| class)
+}
+
+// Moves so that the position left behind is lost for the opponent, when there
+// is such a move. Otherwise it stalls: it avoids killing boards so the
+// opponent has as many chances as possible to slip. Either way it only
+// multiplies classes, so more boards don't make it slower to decide.
+pub fn best_notakto_move(notakto: &Notakto) -> Option<NotaktoMove> {
+    let masks: Vec<u16> = notakto.boards.iter().map(board_mask).collect();
+    let classes: Vec<Class> = masks.iter().map(|&mask| class_of(mask)).collect();
+    let mut fallback: Option<(NotaktoMove, bool)> = None;
+
+    for board_index in notakto.live_boards() {
+        let others = classes
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != board_index)
+            .fold(ONE, |product, (_, &class)| product.times(class));
+
+        for index in (0..9).filter(|&index| masks[board_index] & 1 << index == 0) {
+            let next = masks[board_index] | 1 << index;
+            let notakto_move = (board_index, index / 3, index % 3);
+
+            if others.times(class_of(next)).is_losing() {
+                return Some(notakto_move);
+            }
+
+            let kills = is_dead(next);
+            if fallback.is_none_or(|(_, fallback_kills)| fallback_kills && !kills) {
+                fallback = Some((notakto_move, kills));
+            }
+        }
+    }
+
+    fallback.map(|(notakto_move, _)| notakto_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::player::Player;
+    use std::collections::HashMap;
+
+    // Only the live boards matter, and their order doesn't, so a position is
+    // the sorted list of their canonical shapes.
+    fn position_key(masks: &[u16]) -> Vec<u16> {
+        let mut key: Vec<u16> = masks
+            .iter()
+            .filter(|&&mask| !is_dead(mask))
+            .map(|&mask| canonical(mask))
+            .collect();
+        key.sort_unstable();
+        key
+    }
+
+    // Whether the player to move wins, by trying everything. Killing the last
+    // live board loses, so with no live boards left the previous player just
+    // lost.
+    fn wins(key: &[u16], memo: &mut HashMap<Vec<u16>, bool>) -> bool {
+        if key.is_empty() {
+            return true;
+        }
+        if let Some(&result) = memo.get(key) {
+            return result;
+        }
+
+        let result = (0..key.len()).any(|board| {
+            (0..9)
+                .filter(|&index| key[board] & 1 << index == 0)
+                .any(|index| {
+                    let mut next = key.to_vec();
+                    next[board] |= 1 << index;
+                    !wins(&position_key(&next), memo)
+                })
+        });
+
+        memo.insert(key.to_vec(), result);
+        result
+    }
+
+    fn live_shapes() -> Vec<u16> {
+        let mut shapes: Vec<u16> = (0..512)
+            .filter(|&mask| !is_dead(mask))
+            .map(canonical)
+            .collect();
+        shapes.sort_unstable();
+        shapes.dedup();
+        shapes
+    }
+
+    #[test]
+    fn every_live_board_has_a_class() {
+        let shapes = live_shapes();
+        assert_eq!(shapes.len(), CLASSES.len());
+        for shape in shapes {
+            assert!(CLASSES.iter().any(|&(mask, _)| mask == shape));
+        }
+    }
+
+    #[test]
+    fn the_quotient_has_eighteen_classes() {
+        let generators = [A, B, C, D];
+        let mut classes = vec![ONE];
+        let mut index = 0;
+        while index < classes.len() {
+            for generator in generators {
+                let product = classes[index].times(generator);
+                if !classes.contains(&product) {
+                    classes.push(product);
+                }
+            }
+            index += 1;
+        }
+        assert_eq!(classes.len(), 18);
+    }
+
+    #[test]
+    fn classes_agree_with_the_search_on_up_to_three_boards() {
+        let shapes = live_shapes();
+        let mut memo = HashMap::new();
+
+        for (first_index, &first) in shapes.iter().enumerate() {
+            for (second_index, &second) in shapes.iter().enumerate().skip(first_index) {
+                for &third in shapes.iter().skip(second_index) {
+                    let position = [first, second, third];
+                    let product = position
+                        .iter()
+                        .fold(ONE, |product, &mask| product.times(class_of(mask)));
+                    assert_eq!(
+                        product.is_losing(),
+                        !wins(&position_key(&position), &mut memo),
+                        "{:?}",
+                        position
+                    );
+                }
+
+                let product = class_of(first).times(class_of(second));
+                assert_eq!(
+                    product.is_losing(),
+                    !wins(&position_key(&[first, second]), &mut memo)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn many_boards_are_decided_right_away() {
+        let notakto = Notakto::new(40, &Player::X);
+        assert!(best_notakto_move(&notakto).is_some());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::gobblet::PieceSize;
+use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use crate::models::variant::Variant;
+use crate::utils::ai::{current_player, winning_player_on};
+use crate::utils::validation::{classify_position, count_markers, PositionStatus};
+use regex::Regex;
+use std::fmt;
+
+pub fn row_letter(row_index: usize) -> char {
+    if row_index < 26 {
+        (b'A' + row_index as u8) as char
+    } else {
+        '?'
+    }
+}
+
+pub fn cell_name(row_index: usize, col_index: usize) -> String {
+    format!("{}{}", row_letter(row_index), col_index + 1)
+}
+
+pub fn cube_cell_name(position: (usize, usize, usize)) -> String {
+    let (layer_index, row_index, col_index) = position;
+    format!("{}{}", layer_index + 1, cell_name(row_index, col_index))
+}
+
+// A cell with the symbol placed on it, written like a drop in chess: "O@B2".
+pub fn symbol_cell_name(symbol: Symbol, row_index: usize, col_index: usize) -> String {
+    format!("{}@{}", symbol.character(), cell_name(row_index, col_index))
+}
+
+pub fn parse_symbol_cell_name(name: &str) -> Option<(Symbol, usize, usize)> {
+    let (symbol, cell) = name.trim().split_once('@')?;
+    let symbol = match symbol.chars().collect::<Vec<char>>()[..] {
+        [character] => Symbol::from_character(character)?,
+        _ => return None,
+    };
+    let (row_index, col_index) = parse_cell_name(cell)?;
+    Some((symbol, row_index, col_index))
+}
+
+pub fn number_cell_name(number: u8, row_index: usize, col_index: usize) -> String {
+    format!("{}@{}", number, cell_name(row_index, col_index))
+}
+
+pub fn parse_number_cell_name(name: &str) -> Option<(u8, usize, usize)> {
+    let (number, cell) = name.trim().split_once('@')?;
+    let number = number.parse::<u8>().ok()?;
+    let (row_index, col_index) = parse_cell_name(cell)?;
+    Some((number, row_index, col_index))
+}
+
+// A piece put down from the reserve, by its size: "L@B2".
+pub fn sized_cell_name(size: PieceSize, row_index: usize, col_index: usize) -> String {
+    format!("{}@{}", size.letter(), cell_name(row_index, col_index))
+}
+
+pub fn parse_sized_cell_name(name: &str) -> Option<(PieceSize, usize, usize)> {
+    let (size, cell) = name.trim().split_once('@')?;
+    let mut letters = size.chars();
+    let size = match (letters.next(), letters.next()) {
+        (Some(letter), None) => PieceSize::from_letter(letter)?,
+        _ => return None,
+    };
+    let (row_index, col_index) = parse_cell_name(cell)?;
+    Some((size, row_index, col_index))
+}
+
+pub fn parse_cube_cell_name(name: &str) -> Option<(usize, usize, usize)> {
+    let cube_cell_name_regex = Regex::new(r"^\s*(\d+)\W*([A-Za-z]\d+)\s*$").unwrap();
+    let captures = cube_cell_name_regex.captures(name)?;
+    let layer_index = captures[1].parse::<usize>().ok()?.checked_sub(1)?;
+    let (row_index, col_index) = parse_cell_name(&captures[2])?;
+    Some((layer_index, row_index, col_index))
+}
+
+pub fn parse_cell_name(name: &str) -> Option<(usize, usize)> {
+    let cell_name_regex = Regex::new(r"^\s*([A-Za-z])(\d+)\s*$").unwrap();
+    let captures = cell_name_regex.captures(name)?;
+    let row_letter = captures[1].to_ascii_uppercase().chars().next()?;
+    let row_index = (row_letter as u8 - b'A') as usize;
+    let col_index = captures[2].parse::<usize>().ok()?.checked_sub(1)?;
+    Some((row_index, col_index))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PositionError {
+    Malformed(String),
+    Rejected(PositionStatus),
+    WrongSideToMove(Player),
+    WinnerCannotMove(Player),
+    UnsupportedVariant(Variant),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::Malformed(position) => write!(
+                f,
+                "'{}' isn't a position (expected something like 'XO_/_X_/__O x')",
+                position
+            ),
+            PositionError::Rejected(status) => write!(f, "{}", status),
+            PositionError::WrongSideToMove(player) => write!(
+                f,
+                "It can't be {}'s turn with these marker counts",
+                player.character()
+            ),
+            PositionError::WinnerCannotMove(player) => write!(
+                f,
+                "Player {} already won, so it can't be their turn",
+                player.character()
+            ),
+            PositionError::UnsupportedVariant(variant) => write!(
+                f,
+                "Starting positions aren't supported for {} games",
+                variant.name()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+fn marker_character(cell: &Cell) -> char {
+    match cell {
+        Cell::Marker(Player::X) => 'X',
+        Cell::Marker(Player::O) => 'O',
+        Cell::Symbol(Symbol::X) => 'X',
+        Cell::Symbol(Symbol::O) => 'O',
+        Cell::Marker(Player::Y) => 'Y',
+        Cell::Marker(Player::Z) => 'Z',
+        Cell::Empty => '_',
+        Cell::Blocked => '#',
+        Cell::Number(number) => char::from(b'0' + number),
+    }
+}
+
+pub fn position_string(board: &Board, side_to_move: &Player) -> String {
+    let rows: Vec<String> = board
+        .rows
+        .iter()
+        .map(|row| row.cells.iter().map(marker_character).collect())
+        .collect();
+
+    format!(
+        "{} {}",
+        rows.join("/"),
+        side_to_move.character().to_ascii_lowercase()
+    )
+}
+
+// The player who moved first is whoever isn't behind on markers; with equal
+// counts that's whoever is on move now.
+pub fn first_player_for(board: &Board, side_to_move: &Player) -> Player {
+    let x_count = count_markers(board, &Player::X);
+    let o_count = count_markers(board, &Player::O);
+
+    if x_count == o_count {
+        side_to_move.clone()
+    } else {
+        side_to_move.opponent()
+    }
+}
+
+pub fn validate_position(board: &Board, side_to_move: &Player) -> Result<(), PositionError> {
+    let status = classify_position(board);
+    if status != PositionStatus::Legal {
+        return Err(PositionError::Rejected(status));
+    }
+
+    let first_player = first_player_for(board, side_to_move);
+    if &current_player(board, &first_player) != side_to_move {
+        return Err(PositionError::WrongSideToMove(side_to_move.clone()));
+    }
+
+    match winning_player_on(board) {
+        Some(winner) if &winner == side_to_move => Err(PositionError::WinnerCannotMove(winner)),
+        _ => Ok(()),
+    }
+}
+
+pub fn read_position(position: &str) -> Result<(Board, Player), PositionError> {
+    let position_regex =
+        Regex::new(r"(?i)^\s*([XO_]{3})/([XO_]{3})/([XO_]{3})\s+([XO])\s*$").unwrap();
+    let captures = position_regex
+        .captures(position)
+        .ok_or_else(|| PositionError::Malformed(String::from(position.trim())))?;
+
+    let mut board = Board::new();
+    for row_index in 0..3 {
+        for (col_index, character) in captures[row_index + 1].chars().enumerate() {
+            let cell = match character.to_ascii_uppercase() {
+                'X' => Cell::Marker(Player::X),
+                'O' => Cell::Marker(Player::O),
+                _ => Cell::Empty,
+            };
+            board.rows[row_index].cells[col_index] = cell;
+        }
+    }
+
+    let side_to_move = if captures[4].eq_ignore_ascii_case("x") {
+        Player::X
+    } else {
+        Player::O
+    };
+
+    Ok((board, side_to_move))
+}
+
+pub fn parse_position(position: &str) -> Result<(Board, Player), PositionError> {
+    let (board, side_to_move) = read_position(position)?;
+    validate_position(&board, &side_to_move)?;
+    Ok((board, side_to_move))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::validation::PositionProblem;
+
+    #[test]
+    fn positions_round_trip_through_their_string() {
+        let (board, side_to_move) = parse_position("XO_/_X_/__O x").unwrap();
+        assert_eq!(side_to_move, Player::X);
+        assert_eq!(board.rows[0].cells[1], Cell::Marker(Player::O));
+        assert_eq!(position_string(&board, &side_to_move), "XO_/_X_/__O x");
+    }
+
+    #[test]
+    fn positions_are_read_case_insensitively() {
+        assert!(parse_position("  xo_/___/___ X ").is_ok());
+    }
+
+    #[test]
+    fn malformed_positions_are_rejected() {
+        for position in [
+            "XO_/___ x",
+            "XO_/___/___",
+            "XOA/___/___ x",
+            "XO__/___/___ o",
+        ] {
+            assert_eq!(
+                parse_position(position).err(),
+                Some(PositionError::Malformed(String::from(position)))
+            );
+        }
+    }
+
+    #[test]
+    fn impossible_marker_counts_are_rejected() {
+        assert_eq!(
+            parse_position("XX_/___/___ o").err(),
+            Some(PositionError::Rejected(PositionStatus::Illegal(
+                PositionProblem::MarkerCounts {
+                    x_count: 2,
+                    o_count: 0
+                }
+            )))
+        );
+    }
+
+    #[test]
+    fn the_side_to_move_has_to_fit_the_marker_counts() {
+        assert_eq!(
+            parse_position("XX_/O__/___ x").err(),
+            Some(PositionError::WrongSideToMove(Player::X))
+        );
+    }
+
+    #[test]
+    fn the_winner_cannot_be_on_move() {
+        assert_eq!(
+            parse_position("XXX/OO_/O__ x").err(),
+            Some(PositionError::WinnerCannotMove(Player::X))
+        );
+    }
+
+    #[test]
+    fn the_player_behind_on_markers_moved_second() {
+        let (board, side_to_move) = read_position("X__/___/___ o").unwrap();
+        assert_eq!(first_player_for(&board, &side_to_move), Player::X);
+    }
+}
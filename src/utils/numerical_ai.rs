@@ -0,0 +1,35 @@
+use crate::models::cell::Cell;
+use crate::models::numerical::{Numerical, TARGET_SUM};
+use crate::models::player::Player;
+use crate::utils::rules_ai::WIN_SCORE;
+
+pub const SEARCH_DEPTH: usize = 3;
+
+// Whether the player to move could finish a line straight away: two numbers
+// down and the one that makes fifteen still in their hand.
+fn can_finish_line(numerical: &Numerical, to_move: &Player) -> bool {
+    let numbers = numerical.remaining(to_move);
+    numerical
+        .board
+        .get_cell_position_lines()
+        .iter()
+        .any(|line| {
+            let (sum, empty) =
+                line.iter()
+                    .fold((0u8, 0), |(sum, empty), (cell, _, _)| match cell {
+                        Cell::Number(number) => (sum + number, empty),
+                        _ => (sum, empty + 1),
+                    });
+            empty == 1 && sum < TARGET_SUM && numbers.contains(&(TARGET_SUM - sum))
+        })
+}
+
+// Scored for the player to move. Past the search horizon, a line they can
+// finish next counts as nearly won.
+pub fn evaluate(numerical: &Numerical, to_move: &Player) -> i32 {
+    if can_finish_line(numerical, to_move) {
+        WIN_SCORE / 2
+    } else {
+        0
+    }
+}
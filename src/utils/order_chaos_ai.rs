@@ -0,0 +1,42 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::order_chaos::Role;
+use crate::models::symbol::Symbol;
+use crate::utils::lines::{line_weight, Lines};
+
+// The same search serves both roles: Order maximizes the open lines and
+// Chaos minimizes them.
+pub const SEARCH_DEPTH: usize = 2;
+
+// Scored for Order: every line still holding a single symbol is a chance at
+// five, worth more the fuller it is. Lines with both symbols are Chaos's.
+pub fn evaluate(board: &Board, lines: &Lines) -> i32 {
+    (0..lines.cells.len())
+        .map(
+            |line_index| match lines.symbols(board, line_index, Symbol::X) {
+                (count, 0) | (0, count) => line_weight(count),
+                _ => 0,
+            },
+        )
+        .sum()
+}
+
+// Cells on the most lines first, which are the ones that matter most to both
+// sides and make the pruning bite sooner.
+pub fn ordered_positions(board: &Board, lines: &Lines) -> Vec<(usize, usize)> {
+    let mut positions: Vec<(usize, usize)> = board
+        .get_cell_positions()
+        .iter()
+        .filter(|(cell, _, _)| **cell == Cell::Empty)
+        .map(|&(_, row_index, col_index)| (row_index, col_index))
+        .collect();
+    positions.sort_by_key(|&position| std::cmp::Reverse(lines.through(position).len()));
+    positions
+}
+
+pub fn score_for(role: Role, order_score: i32) -> i32 {
+    match role {
+        Role::Order => order_score,
+        Role::Chaos => -order_score,
+    }
+}
@@ -0,0 +1,115 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::classic::Classic;
+use crate::models::phantom::{Phantom, Position};
+use crate::models::player::Player;
+use crate::models::variant::Variant;
+use crate::utils::ai::{current_player, winning_player_on};
+use crate::utils::rules_ai::rules_move_scores;
+
+// Finding a mark costs nothing and teaches something, so it's worth a little
+// more than the move that follows it.
+const FOUND_MARK_BONUS: i32 = 1;
+
+// Every way of choosing `count` of the cells, in order.
+fn choose(cells: &[Position], count: usize) -> Vec<Vec<Position>> {
+    if count == 0 {
+        return vec![vec![]];
+    }
+    if cells.len() < count {
+        return vec![];
+    }
+
+    let mut with_first: Vec<Vec<Position>> = choose(&cells[1..], count - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, cells[0]);
+            rest
+        })
+        .collect();
+    with_first.extend(choose(&cells[1..], count));
+    with_first
+}
+
+// Every full board consistent with what the player knows: the other player's
+// unfound marks could be in any of the cells the player hasn't ruled out, as
+// long as they wouldn't already have ended the game.
+fn possible_boards(
+    view: &Board,
+    unknown: &[Position],
+    hidden: usize,
+    opponent: &Player,
+) -> Vec<Board> {
+    choose(unknown, hidden)
+        .into_iter()
+        .map(|cells| {
+            let mut board = view.clone();
+            for (row_index, col_index) in cells {
+                board.rows[row_index].cells[col_index] = Cell::Marker(opponent.clone());
+            }
+            board
+        })
+        .filter(|board| winning_player_on(board).is_none())
+        .collect()
+}
+
+// What putting a mark on each empty cell of a full-information board is
+// worth, by solving the rest of the game as if nothing were hidden.
+fn placement_values(board: &Board, player: &Player) -> Vec<(Position, i32)> {
+    // Whoever went first, it's the player's turn on every possible board.
+    let first_player = if current_player(board, player) == *player {
+        player.clone()
+    } else {
+        player.opponent()
+    };
+    let remaining = board
+        .get_cells()
+        .iter()
+        .filter(|cell| ***cell == Cell::Empty)
+        .count();
+
+    rules_move_scores(
+        &Classic::new(board.clone(), &first_player, Variant::Classic),
+        remaining,
+    )
+}
+
+// Picks the cell to try by averaging over every board the player might be
+// facing, with room in the totals for a win on every one of them. Where a
+// guessed mark already sits on the cell, trying it reveals the mark and the
+// player goes again, so it's worth that board's best move.
+pub fn best_phantom_probe(phantom: &Phantom, player: &Player) -> Option<Position> {
+    let view = phantom.view(player);
+    let unknown: Vec<Position> = view
+        .get_cell_positions()
+        .iter()
+        .filter(|(cell, _, _)| **cell == Cell::Empty)
+        .map(|&(_, row_index, col_index)| (row_index, col_index))
+        .collect();
+    let boards = possible_boards(
+        &view,
+        &unknown,
+        phantom.hidden_count(player),
+        &player.opponent(),
+    );
+
+    let mut totals = vec![0_i64; unknown.len()];
+    for board in &boards {
+        let values = placement_values(board, player);
+        let best = values.iter().map(|&(_, score)| score).max().unwrap_or(0);
+        for (total, position) in totals.iter_mut().zip(&unknown) {
+            *total += i64::from(
+                values
+                    .iter()
+                    .find(|(cell, _)| cell == position)
+                    .map_or(best + FOUND_MARK_BONUS, |&(_, score)| score),
+            );
+        }
+    }
+
+    unknown
+        .iter()
+        .zip(totals)
+        .max_by_key(|&(_, total)| total)
+        .map(|(&position, _)| position)
+}
@@ -0,0 +1,122 @@
+use crate::models::cell::Cell;
+use crate::models::player::Player;
+use crate::models::quantum::{Position, Quantum, QuantumMove};
+use crate::utils::ai::win_arrangements;
+
+const WIN_SCORE: i32 = 1_000;
+
+fn evaluate(quantum: &Quantum, for_player: &Player) -> i32 {
+    if let Some(outcome) = quantum.quantum_outcome() {
+        return match (outcome.winner, outcome.runner_up) {
+            (Some(winner), _) if &winner == for_player => WIN_SCORE,
+            (Some(_), Some(runner_up)) if &runner_up == for_player => -WIN_SCORE / 2,
+            (Some(_), _) => -WIN_SCORE,
+            (None, _) => 0,
+        };
+    }
+
+    // Otherwise judge the settled marks alone: lines still open to one player
+    // are worth more the more of them are already down.
+    let board = quantum.classical_board();
+    let mine = Cell::Marker(for_player.clone());
+    win_arrangements(&board)
+        .iter()
+        .map(|line| {
+            let (own, other) = line
+                .iter()
+                .fold((0, 0), |(own, other), (cell, _, _)| match cell {
+                    Cell::Empty => (own, other),
+                    cell if **cell == mine => (own + 1, other),
+                    _ => (own, other + 1),
+                });
+            match (own, other) {
+                (own, 0) => own * own,
+                (0, other) => -other * other,
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+pub fn spooky_pairs(quantum: &Quantum) -> Vec<(Position, Position)> {
+    let open = quantum.open_positions();
+    open.iter()
+        .enumerate()
+        .flat_map(|(index, &first)| open[index + 1..].iter().map(move |&second| (first, second)))
+        .collect()
+}
+
+// The collapse the chooser likes best, with its value to them.
+pub fn best_collapse(quantum: &Quantum, chooser: &Player) -> Option<(Position, i32)> {
+    let mark = &quantum.marks[quantum.pending_collapse?];
+    [mark.cells.0, mark.cells.1]
+        .iter()
+        .filter_map(|&position| {
+            let mut collapsed = quantum.clone();
+            collapsed.collapse(chooser, position).ok()?;
+            Some((position, evaluate(&collapsed, chooser)))
+        })
+        .max_by_key(|&(_, score)| score)
+}
+
+// How good a position is for the player about to move if they make their best
+// single move, allowing for the collapse the other player would pick.
+fn reply_value(quantum: &Quantum, to_move: &Player) -> i32 {
+    if quantum.quantum_outcome().is_some() {
+        return evaluate(quantum, to_move);
+    }
+
+    if quantum.needs_classical_move() {
+        let mut next = quantum.clone();
+        let position = next.open_positions()[0];
+        next.apply_classical(to_move, position);
+        return evaluate(&next, to_move);
+    }
+
+    spooky_pairs(quantum)
+        .iter()
+        .map(|&cells| {
+            let mut next = quantum.clone();
+            next.apply_spooky(to_move, cells);
+            match best_collapse(&next, &to_move.opponent()) {
+                Some((_, chooser_score)) => -chooser_score,
+                None => evaluate(&next, to_move),
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Looks two moves ahead: each candidate mark, the collapse the opponent would
+// choose if it closes a cycle, and the opponent's best reply after that.
+pub fn best_quantum_move(quantum: &Quantum, for_player: &Player) -> Option<QuantumMove> {
+    if quantum.needs_classical_move() {
+        return quantum
+            .open_positions()
+            .first()
+            .map(|&position| QuantumMove::Classical(position));
+    }
+
+    let opponent = for_player.opponent();
+    spooky_pairs(quantum)
+        .iter()
+        .map(|&cells| {
+            let mut next = quantum.clone();
+            let score = if next.apply_spooky(for_player, cells) {
+                [cells.0, cells.1]
+                    .iter()
+                    .filter_map(|&position| {
+                        let mut collapsed = next.clone();
+                        collapsed.collapse(&opponent, position).ok()?;
+                        Some(-reply_value(&collapsed, &opponent))
+                    })
+                    .min()
+                    .unwrap_or(0)
+            } else {
+                -reply_value(&next, &opponent)
+            };
+            (cells, score)
+        })
+        .max_by_key(|&(_, score)| score)
+        .map(|((first, second), _)| QuantumMove::Spooky(first, second))
+}
@@ -0,0 +1,65 @@
+use crate::models::cell::Cell;
+use crate::models::cube::{Cube, CubePosition};
+use crate::models::player::Player;
+use std::cmp::Reverse;
+
+pub const SEARCH_DEPTH: usize = 3;
+const BEAM_WIDTH: usize = 8;
+
+// Weights for a line holding only one player's marks, indexed by how many. A
+// full line ends the game before it's ever evaluated, so its weight only
+// puts winning and blocking first when ranking moves.
+const LINE_WEIGHTS: [i32; 5] = [0, 1, 8, 64, 1_000_000];
+
+fn line_counts(cube: &Cube, line: &[usize], for_player: &Player) -> (usize, usize) {
+    line.iter()
+        .fold((0, 0), |(mine, theirs), &index| match &cube.cells[index] {
+            Cell::Marker(player) if player == for_player => (mine + 1, theirs),
+            Cell::Marker(_) => (mine, theirs + 1),
+            Cell::Empty | Cell::Blocked | Cell::Number(_) | Cell::Symbol(_) => (mine, theirs),
+        })
+}
+
+pub fn evaluate(cube: &Cube, for_player: &Player) -> i32 {
+    cube.lines
+        .iter()
+        .map(|line| match line_counts(cube, line, for_player) {
+            (mine, 0) => LINE_WEIGHTS[mine.min(4)],
+            (0, theirs) => -LINE_WEIGHTS[theirs.min(4)],
+            _ => 0,
+        })
+        .sum()
+}
+
+// How much a single cell matters to either player, judging only by the lines
+// through it. Used to pick which moves are worth searching.
+fn move_potential(cube: &Cube, index: usize, for_player: &Player) -> i32 {
+    cube.lines_through[index]
+        .iter()
+        .map(
+            |&line_index| match line_counts(cube, &cube.lines[line_index], for_player) {
+                (mine, 0) => LINE_WEIGHTS[(mine + 1).min(4)] * 2,
+                (0, theirs) => LINE_WEIGHTS[(theirs + 1).min(4)],
+                _ => 0,
+            },
+        )
+        .sum()
+}
+
+// The few empty cells most worth searching, best first.
+pub fn candidate_moves(cube: &Cube, for_player: &Player) -> Vec<CubePosition> {
+    let mut candidates: Vec<(usize, i32)> = cube
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| **cell == Cell::Empty)
+        .map(|(index, _)| (index, move_potential(cube, index, for_player)))
+        .collect();
+
+    candidates.sort_by_key(|&(_, potential)| Reverse(potential));
+    candidates
+        .iter()
+        .take(BEAM_WIDTH)
+        .map(|&(index, _)| cube.position_of(index))
+        .collect()
+}
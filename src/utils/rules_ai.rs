@@ -0,0 +1,118 @@
+use crate::models::game_record::GameResult;
+use crate::models::player::Player;
+use crate::models::rules::Rules;
+
+// Above anything a variant's `evaluate` can come up with.
+pub const WIN_SCORE: i32 = 100_000_000;
+
+// Negamax with alpha-beta pruning over whatever the rules allow. Wins score
+// higher the sooner they come; anything still open at the depth limit is
+// left to the variant's own evaluation. When a turn schedule gives the same
+// player another move, the score keeps its sign.
+fn negamax<R: Rules>(rules: &R, to_move: &Player, depth: usize, alpha: i32, beta: i32) -> i32 {
+    if let Some(outcome) = rules.outcome() {
+        let score = WIN_SCORE + depth as i32;
+        return match outcome.result {
+            GameResult::Winner(winner) if &winner == to_move => score,
+            GameResult::Winner(_) => -score,
+            GameResult::Draw | GameResult::Unfinished => 0,
+        };
+    }
+
+    if depth == 0 {
+        return rules.evaluate(to_move);
+    }
+
+    let mut alpha = alpha;
+    for game_move in rules.candidate_moves() {
+        let mut next = rules.clone();
+        next.apply(to_move, game_move);
+
+        let next_player = next.current_player();
+        let score = if &next_player == to_move {
+            negamax(&next, to_move, depth - 1, alpha, beta)
+        } else {
+            -negamax(&next, &next_player, depth - 1, -beta, -alpha)
+        };
+
+        if score >= beta {
+            return score;
+        }
+        alpha = alpha.max(score);
+    }
+
+    alpha
+}
+
+// What each legal move is worth to whoever is to play, looking `depth` moves
+// ahead. Unlike `best_rules_move`, every score is exact rather than a bound.
+pub fn rules_move_scores<R: Rules>(rules: &R, depth: usize) -> Vec<(R::Move, i32)> {
+    let player = rules.current_player();
+
+    rules
+        .legal_moves()
+        .into_iter()
+        .map(|game_move| {
+            let mut next = rules.clone();
+            next.apply(&player, game_move);
+
+            let next_player = next.current_player();
+            let score = if next_player == player {
+                negamax(
+                    &next,
+                    &player,
+                    depth.saturating_sub(1),
+                    -WIN_SCORE * 2,
+                    WIN_SCORE * 2,
+                )
+            } else {
+                -negamax(
+                    &next,
+                    &next_player,
+                    depth.saturating_sub(1),
+                    -WIN_SCORE * 2,
+                    WIN_SCORE * 2,
+                )
+            };
+            (game_move, score)
+        })
+        .collect()
+}
+
+// The best move for whoever is to play, looking `depth` moves ahead. Ties go
+// to whichever the variant puts first.
+pub fn best_rules_move<R: Rules>(rules: &R, depth: usize) -> Option<R::Move> {
+    let player = rules.current_player();
+    let mut best: Option<(R::Move, i32)> = None;
+
+    for game_move in rules.candidate_moves() {
+        let mut next = rules.clone();
+        next.apply(&player, game_move);
+
+        let best_score = best.map_or(-WIN_SCORE * 2, |(_, score)| score);
+        let next_player = next.current_player();
+        let score = if next_player == player {
+            negamax(
+                &next,
+                &player,
+                depth.saturating_sub(1),
+                best_score,
+                WIN_SCORE * 2,
+            )
+        } else {
+            -negamax(
+                &next,
+                &next_player,
+                depth.saturating_sub(1),
+                -WIN_SCORE * 2,
+                -best_score,
+            )
+        };
+
+        if best.is_none() || score > best_score {
+            best = Some((game_move, score));
+        }
+    }
+
+    best.map(|(game_move, _)| game_move)
+}
@@ -0,0 +1,59 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::meta_board::MetaBoard;
+use crate::models::player::Player;
+use crate::utils::ai::win_arrangements;
+
+const CLAIM_WEIGHT: i32 = 40;
+
+// Open lines count for whoever is the only one in them; two in an open line
+// is worth a lot more than one.
+pub fn line_potential(board: &Board, for_player: &Player) -> i32 {
+    let mine = Cell::Marker(for_player.clone());
+    let theirs = Cell::Marker(for_player.opponent());
+
+    win_arrangements(board)
+        .iter()
+        .map(|cell_positions| {
+            let my_count = cell_positions
+                .iter()
+                .filter(|(cell, _, _)| **cell == mine)
+                .count();
+            let their_count = cell_positions
+                .iter()
+                .filter(|(cell, _, _)| **cell == theirs)
+                .count();
+
+            match (my_count, their_count) {
+                (0, 0) => 0,
+                (count, 0) => (count * count) as i32,
+                (0, count) => -((count * count) as i32),
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+// Lines on the claims count for more than lines on the boards still open.
+pub fn evaluate(meta_board: &MetaBoard, for_player: &Player) -> i32 {
+    let claims = meta_board.claims();
+    let claim_score = line_potential(&claims, for_player) * CLAIM_WEIGHT;
+    let board_score: i32 = claims
+        .get_cell_positions()
+        .iter()
+        .filter(|(claim, _, _)| **claim == Cell::Empty)
+        .map(|&(_, board_row, board_col)| {
+            line_potential(&meta_board.boards[board_row][board_col], for_player)
+        })
+        .sum();
+
+    claim_score + board_score
+}
+
+pub fn search_depth(meta_board: &MetaBoard) -> usize {
+    if meta_board.available_boards().len() > 1 {
+        3
+    } else {
+        4
+    }
+}
@@ -0,0 +1,342 @@
+use crate::error::MoveError;
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::player::Player;
+use crate::models::symbol::Symbol;
+use crate::utils::ai::{current_player, game_over, win_arrangements, winning_players_on};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum PositionProblem {
+    MarkerCounts { x_count: usize, o_count: usize },
+    MultipleWinners,
+    WinnerBehindOnMarkers(Player),
+    DisjointWinningLines(Player),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PositionStatus {
+    Legal,
+    Illegal(PositionProblem),
+    Unreachable(PositionProblem),
+}
+
+impl fmt::Display for PositionProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionProblem::MarkerCounts { x_count, o_count } => write!(
+                f,
+                "X has {} markers and O has {}, but players alternate turns",
+                x_count, o_count
+            ),
+            PositionProblem::MultipleWinners => {
+                write!(f, "Both players can't have three in a row")
+            }
+            PositionProblem::WinnerBehindOnMarkers(player) => write!(
+                f,
+                "Player {} has three in a row but has fewer markers, so the game would have ended before the last move",
+                player.character()
+            ),
+            PositionProblem::DisjointWinningLines(player) => write!(
+                f,
+                "Player {} has lines that don't share a cell, so one of them was finished after the game ended",
+                player.character()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PositionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionStatus::Legal => write!(f, "Legal position"),
+            PositionStatus::Illegal(problem) => write!(f, "Illegal position: {}", problem),
+            PositionStatus::Unreachable(problem) => {
+                write!(f, "Unreachable position: {}", problem)
+            }
+        }
+    }
+}
+
+pub fn count_markers(board: &Board, player: &Player) -> usize {
+    board
+        .get_cells()
+        .iter()
+        .filter(|cell| ***cell == Cell::Marker(player.clone()))
+        .count()
+}
+
+// Every winning line of the winner has to run through the cell they played
+// last; otherwise the game would have ended on an earlier move.
+fn winning_lines_share_a_cell(board: &Board, winner: &Player) -> bool {
+    let winner_cell = Cell::Marker(winner.clone());
+    let winning_lines: Vec<Vec<(usize, usize)>> = win_arrangements(board)
+        .iter()
+        .filter(|cell_positions| {
+            cell_positions
+                .iter()
+                .all(|(cell, _, _)| **cell == winner_cell)
+        })
+        .map(|cell_positions| {
+            cell_positions
+                .iter()
+                .map(|&(_, row_index, col_index)| (row_index, col_index))
+                .collect()
+        })
+        .collect();
+
+    match winning_lines.split_first() {
+        Some((first_line, other_lines)) => first_line
+            .iter()
+            .any(|position| other_lines.iter().all(|line| line.contains(position))),
+        None => true,
+    }
+}
+
+pub fn classify_position(board: &Board) -> PositionStatus {
+    let x_count = count_markers(board, &Player::X);
+    let o_count = count_markers(board, &Player::O);
+
+    if x_count.max(o_count) - x_count.min(o_count) > 1 {
+        return PositionStatus::Illegal(PositionProblem::MarkerCounts { x_count, o_count });
+    }
+
+    match winning_players_on(board).as_slice() {
+        [] => PositionStatus::Legal,
+        [winner] => {
+            let (winner_count, loser_count) = if winner == &Player::X {
+                (x_count, o_count)
+            } else {
+                (o_count, x_count)
+            };
+
+            if winner_count < loser_count {
+                PositionStatus::Unreachable(PositionProblem::WinnerBehindOnMarkers(winner.clone()))
+            } else if !winning_lines_share_a_cell(board, winner) {
+                PositionStatus::Unreachable(PositionProblem::DisjointWinningLines(winner.clone()))
+            } else {
+                PositionStatus::Legal
+            }
+        }
+        _ => PositionStatus::Unreachable(PositionProblem::MultipleWinners),
+    }
+}
+
+pub fn validate_move(
+    board: &Board,
+    first_player: &Player,
+    player: &Player,
+    row_index: usize,
+    col_index: usize,
+) -> Result<(), MoveError> {
+    let cell = board.get_cell_at(row_index, col_index)?;
+
+    if game_over(board) {
+        return Err(MoveError::GameOver);
+    }
+
+    let expected = current_player(board, first_player);
+    if &expected != player {
+        return Err(MoveError::WrongPlayer {
+            expected,
+            attempted: player.clone(),
+        });
+    }
+
+    check_empty(cell, row_index, col_index)
+}
+
+// Anything but an empty cell is taken one way or another, and says why.
+pub fn check_empty(cell: &Cell, row_index: usize, col_index: usize) -> Result<(), MoveError> {
+    match cell {
+        Cell::Empty => Ok(()),
+        Cell::Blocked => Err(MoveError::Blocked {
+            row_index,
+            col_index,
+        }),
+        Cell::Number(number) => Err(MoveError::NumberPresent {
+            row_index,
+            col_index,
+            number: *number,
+        }),
+        Cell::Marker(by) => Err(MoveError::Occupied {
+            row_index,
+            col_index,
+            by: by.clone(),
+        }),
+        Cell::Symbol(symbol) => Err(MoveError::SymbolPresent {
+            row_index,
+            col_index,
+            symbol: *symbol,
+        }),
+    }
+}
+
+pub fn play_move(
+    board: &mut Board,
+    first_player: &Player,
+    player: &Player,
+    row_index: usize,
+    col_index: usize,
+) -> Result<(), MoveError> {
+    validate_move(board, first_player, player, row_index, col_index)?;
+    board.set_cell_at(row_index, col_index, Cell::Marker(player.clone()))
+}
+
+// In wild rules the symbols say nothing about who placed them, so the game
+// keeps track of whose turn it is and passes it in as `expected`.
+pub fn play_wild_move(
+    board: &mut Board,
+    expected: &Player,
+    player: &Player,
+    symbol: Symbol,
+    row_index: usize,
+    col_index: usize,
+) -> Result<(), MoveError> {
+    let cell = board.get_cell_at(row_index, col_index)?;
+
+    if expected != player {
+        return Err(MoveError::WrongPlayer {
+            expected: expected.clone(),
+            attempted: player.clone(),
+        });
+    }
+
+    check_empty(cell, row_index, col_index)?;
+
+    board.set_cell_at(row_index, col_index, Cell::Symbol(symbol))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::notation::read_position;
+
+    fn board(position: &str) -> Board {
+        read_position(position).unwrap().0
+    }
+
+    #[test]
+    fn reachable_positions_are_legal() {
+        assert_eq!(classify_position(&Board::new()), PositionStatus::Legal);
+        assert_eq!(
+            classify_position(&board("XO_/_X_/__O x")),
+            PositionStatus::Legal
+        );
+        assert_eq!(
+            classify_position(&board("XXX/OO_/___ o")),
+            PositionStatus::Legal
+        );
+    }
+
+    #[test]
+    fn a_finished_double_line_through_the_last_mark_is_legal() {
+        assert_eq!(
+            classify_position(&board("XXX/XOO/XOO o")),
+            PositionStatus::Legal
+        );
+    }
+
+    #[test]
+    fn marker_counts_too_far_apart_are_illegal() {
+        assert_eq!(
+            classify_position(&board("XXX/___/___ o")),
+            PositionStatus::Illegal(PositionProblem::MarkerCounts {
+                x_count: 3,
+                o_count: 0
+            })
+        );
+    }
+
+    #[test]
+    fn two_winners_are_unreachable() {
+        assert_eq!(
+            classify_position(&board("XXX/OOO/___ x")),
+            PositionStatus::Unreachable(PositionProblem::MultipleWinners)
+        );
+    }
+
+    #[test]
+    fn a_winner_behind_on_markers_is_unreachable() {
+        assert_eq!(
+            classify_position(&board("OOO/XX_/XX_ o")),
+            PositionStatus::Unreachable(PositionProblem::WinnerBehindOnMarkers(Player::O))
+        );
+    }
+
+    #[test]
+    fn moves_are_checked_before_they_are_played() {
+        let mut board = board("X__/___/___ o");
+        assert_eq!(
+            play_move(&mut board, &Player::X, &Player::X, 1, 1),
+            Err(MoveError::WrongPlayer {
+                expected: Player::O,
+                attempted: Player::X
+            })
+        );
+        assert_eq!(
+            play_move(&mut board, &Player::X, &Player::O, 0, 0),
+            Err(MoveError::Occupied {
+                row_index: 0,
+                col_index: 0,
+                by: Player::X
+            })
+        );
+        assert!(play_move(&mut board, &Player::X, &Player::O, 3, 0).is_err());
+        assert_eq!(play_move(&mut board, &Player::X, &Player::O, 1, 1), Ok(()));
+        assert_eq!(board.rows[1].cells[1], Cell::Marker(Player::O));
+    }
+
+    #[test]
+    fn wild_marks_only_go_in_empty_cells() {
+        let mut board = Board::new();
+        board.rows[0].cells[0] = Cell::Symbol(Symbol::X);
+        board.rows[0].cells[1] = Cell::Marker(Player::O);
+        board.rows[0].cells[2] = Cell::Number(5);
+        board.rows[1].cells[0] = Cell::Blocked;
+        let play = |board: &mut Board, row_index, col_index| {
+            play_wild_move(
+                board,
+                &Player::X,
+                &Player::X,
+                Symbol::O,
+                row_index,
+                col_index,
+            )
+        };
+
+        assert_eq!(
+            play(&mut board, 0, 0),
+            Err(MoveError::SymbolPresent {
+                row_index: 0,
+                col_index: 0,
+                symbol: Symbol::X
+            })
+        );
+        assert_eq!(
+            play(&mut board, 0, 1),
+            Err(MoveError::Occupied {
+                row_index: 0,
+                col_index: 1,
+                by: Player::O
+            })
+        );
+        assert_eq!(
+            play(&mut board, 0, 2),
+            Err(MoveError::NumberPresent {
+                row_index: 0,
+                col_index: 2,
+                number: 5
+            })
+        );
+        assert_eq!(
+            play(&mut board, 1, 0),
+            Err(MoveError::Blocked {
+                row_index: 1,
+                col_index: 0
+            })
+        );
+        assert_eq!(play(&mut board, 1, 1), Ok(()));
+        assert_eq!(board.rows[1].cells[1], Cell::Symbol(Symbol::O));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::models::board::Board;
+use crate::models::cell::Cell;
+use crate::models::symbol::Symbol;
+use crate::models::wild::WildMove;
+use crate::utils::ai::{potential_empty_moves, winning_symbol_on};
+use std::collections::HashMap;
+
+// Each cell is empty, X or O, so a board fits in a base-3 number.
+fn board_key(board: &Board) -> u32 {
+    board.get_cells().iter().fold(0, |key, cell| {
+        key * 3
+            + match cell {
+                Cell::Symbol(Symbol::X) => 1,
+                Cell::Symbol(Symbol::O) => 2,
+                Cell::Empty | Cell::Blocked | Cell::Number(_) | Cell::Marker(_) => 0,
+            }
+    })
+}
+
+pub fn wild_moves(board: &Board) -> Vec<WildMove> {
+    potential_empty_moves(board)
+        .iter()
+        .flat_map(|&(_, row_index, col_index)| {
+            Symbol::ALL
+                .iter()
+                .map(move |&symbol| (symbol, row_index, col_index))
+        })
+        .collect()
+}
+
+// Whoever completes a line wins, whatever the symbol, so a position's value
+// only depends on the marks and not on who placed them. That keeps the table
+// small enough to solve the whole game. Scores favor winning sooner.
+fn solve(board: &mut Board, memo: &mut HashMap<u32, i32>) -> i32 {
+    let key = board_key(board);
+    if let Some(&score) = memo.get(&key) {
+        return score;
+    }
+
+    let moves = wild_moves(board);
+    let remaining = moves.len() as i32 / 2;
+    let mut best = if moves.is_empty() { 0 } else { i32::MIN };
+
+    for (symbol, row_index, col_index) in moves {
+        board.rows[row_index].cells[col_index] = Cell::Symbol(symbol);
+        let score = if winning_symbol_on(board).is_some() {
+            remaining
+        } else {
+            -solve(board, memo)
+        };
+        board.rows[row_index].cells[col_index] = Cell::Empty;
+
+        best = best.max(score);
+        if best == remaining {
+            break;
+        }
+    }
+
+    memo.insert(key, best);
+    best
+}
+
+pub fn best_wild_move(board: &Board) -> Option<WildMove> {
+    let mut scratch = board.clone();
+    let mut memo = HashMap::new();
+    let mut best: Option<(WildMove, i32)> = None;
+
+    for (symbol, row_index, col_index) in wild_moves(board) {
+        scratch.rows[row_index].cells[col_index] = Cell::Symbol(symbol);
+        let score = if winning_symbol_on(&scratch).is_some() {
+            i32::MAX
+        } else {
+            -solve(&mut scratch, &mut memo)
+        };
+        scratch.rows[row_index].cells[col_index] = Cell::Empty;
+
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_score)| score > *best_score)
+        {
+            best = Some(((symbol, row_index, col_index), score));
+        }
+    }
+
+    best.map(|(wild_move, _)| wild_move)
+}